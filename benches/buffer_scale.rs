@@ -0,0 +1,41 @@
+use criterion::*;
+use isochronous_finite_fields::{slice::scale, GF};
+
+/// Scales with a lookup table of all products with the coefficient. This is what a table based
+/// implementation does, which is faster but leaks the input through the memory access pattern.
+fn scale_table(table: &[GF; 256], input: &[GF], output: &mut [GF]) {
+    for (o, &i) in output.iter_mut().zip(input) {
+        *o = table[i.0 as usize];
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut buffer_scale = c.benchmark_group("buffer scale");
+    let coefficient = GF(0x53);
+    let mut table = [GF(0); 256];
+    for (x, product) in table.iter_mut().enumerate() {
+        *product = coefficient * GF(x as u8);
+    }
+
+    for size in [1 << 10, 1 << 16, 1 << 20] {
+        let input: Vec<GF> = (0..size).map(|i| GF((i * 31 + 7) as u8)).collect();
+        let mut output = vec![GF(0); size];
+        buffer_scale.throughput(Throughput::Bytes(size as u64));
+
+        buffer_scale.bench_with_input(
+            BenchmarkId::new("isochronous", size),
+            &input,
+            |bench, input| {
+                bench.iter(|| scale(black_box(coefficient), black_box(input), &mut output))
+            },
+        );
+        buffer_scale.bench_with_input(BenchmarkId::new("table", size), &input, |bench, input| {
+            bench.iter(|| scale_table(black_box(&table), black_box(input), &mut output))
+        });
+    }
+
+    buffer_scale.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
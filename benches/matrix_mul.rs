@@ -0,0 +1,30 @@
+use criterion::*;
+use isochronous_finite_fields::{GFMatrix, GF};
+
+/// The textbook triple loop, as a baseline for the blocked multiplication.
+fn naive(a: &GFMatrix, b: &GFMatrix) -> GFMatrix {
+    GFMatrix::from_fn(a.rows(), b.cols(), |i, j| {
+        (0..a.cols()).fold(GF(0), |acc, k| acc + a[(i, k)] * b[(k, j)])
+    })
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut matrix_mul = c.benchmark_group("matrix mul");
+
+    for n in [32, 128, 256] {
+        let a = GFMatrix::from_fn(n, n, |i, j| GF((i * 31 + j * 17) as u8));
+        let b = GFMatrix::from_fn(n, n, |i, j| GF((i * 7 + j * 13 + 1) as u8));
+
+        matrix_mul.bench_with_input(BenchmarkId::new("naive", n), &(&a, &b), |bench, (a, b)| {
+            bench.iter(|| naive(black_box(a), black_box(b)))
+        });
+        matrix_mul.bench_with_input(BenchmarkId::new("mul", n), &(&a, &b), |bench, (a, b)| {
+            bench.iter(|| black_box(*a) * black_box(*b))
+        });
+    }
+
+    matrix_mul.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
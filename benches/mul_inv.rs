@@ -1,5 +1,5 @@
 use criterion::*;
-use isochronous_finite_fields::GF;
+use isochronous_finite_fields::{InverseMethod, GF};
 
 fn criterion_benchmark(c: &mut Criterion) {
     let mut mul_inv = c.benchmark_group("multiplicative inverse");
@@ -11,6 +11,20 @@ fn criterion_benchmark(c: &mut Criterion) {
     }
 
     mul_inv.finish();
+
+    let mut inverse_method = c.benchmark_group("inverse method");
+
+    for method in [
+        InverseMethod::BruteForce,
+        InverseMethod::Fermat,
+        InverseMethod::ItohTsujii,
+    ] {
+        inverse_method.bench_with_input(format!("{method:?}"), &method, |b, method| {
+            b.iter(|| black_box(GF(148)).inverse_with(*method))
+        });
+    }
+
+    inverse_method.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
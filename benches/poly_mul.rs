@@ -0,0 +1,25 @@
+use criterion::*;
+use isochronous_finite_fields::{GFPoly, GF};
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut poly_mul = c.benchmark_group("poly mul");
+
+    for n in [256, 1024, 4096] {
+        let a = GFPoly::new((0..n).map(|i| GF((i * 31 + 5) as u8)).collect());
+        let b = GFPoly::new((0..n).map(|i| GF((i * 7 + 1) as u8)).collect());
+
+        poly_mul.bench_with_input(
+            BenchmarkId::new("schoolbook", n),
+            &(&a, &b),
+            |bench, (a, b)| bench.iter(|| black_box(*a) * black_box(*b)),
+        );
+        poly_mul.bench_with_input(BenchmarkId::new("toom3", n), &(&a, &b), |bench, (a, b)| {
+            bench.iter(|| black_box(*a).mul_toom3(black_box(*b)))
+        });
+    }
+
+    poly_mul.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,17 @@
+use criterion::*;
+use isochronous_finite_fields::GF;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut pow = c.benchmark_group("pow");
+
+    for a in [0, 64, 128, 196, 255].windows(2) {
+        pow.bench_with_input(format!("{a:?}"), a, |b, a| {
+            b.iter(|| black_box(GF(a[0])).pow(black_box(a[1] as u32)))
+        });
+    }
+
+    pow.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
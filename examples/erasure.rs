@@ -0,0 +1,98 @@
+//! Erasure codes a file into data and parity shards, deletes some of the shards, and recovers
+//! the file from the remaining ones.
+//!
+//! ```text
+//! cargo run --example erasure -- <file> [data shards] [parity shards] [shard directory]
+//! ```
+
+use isochronous_finite_fields::ReedSolomon;
+use std::error::Error;
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("error: {error}");
+        process::exit(1);
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args().skip(1);
+    let file = match args.next() {
+        Some(file) => PathBuf::from(file),
+        None => {
+            eprintln!("usage: erasure <file> [data shards] [parity shards] [shard directory]");
+            process::exit(2);
+        }
+    };
+    let data_shards: usize = args.next().map_or(Ok(4), |s| s.parse())?;
+    let parity_shards: usize = args.next().map_or(Ok(2), |s| s.parse())?;
+    let directory = args
+        .next()
+        .map_or_else(|| env::temp_dir().join("erasure-shards"), PathBuf::from);
+
+    let rs = ReedSolomon::new(data_shards, parity_shards)
+        .map_err(|e| format!("invalid number of shards: {e}"))?;
+    let original = fs::read(&file)?;
+
+    // Split the file into equally long data shards, padded with zeros at the end.
+    let shard_len = usize::max(1, original.len().div_ceil(data_shards));
+    let mut shards: Vec<Vec<u8>> = (0..rs.total_shards())
+        .map(|i| {
+            let start = usize::min(i * shard_len, original.len());
+            let end = usize::min(start + shard_len, original.len());
+            let mut shard = if i < data_shards {
+                original[start..end].to_vec()
+            } else {
+                Vec::new()
+            };
+            shard.resize(shard_len, 0);
+            shard
+        })
+        .collect();
+    rs.encode_shards(&mut shards)?;
+
+    fs::create_dir_all(&directory)?;
+    let paths: Vec<PathBuf> = (0..rs.total_shards())
+        .map(|i| directory.join(format!("shard-{i:03}")))
+        .collect();
+    for (path, shard) in paths.iter().zip(&shards) {
+        fs::write(path, shard)?;
+    }
+    println!(
+        "wrote {} data and {} parity shards of {} bytes to {}",
+        data_shards,
+        parity_shards,
+        shard_len,
+        directory.display()
+    );
+
+    // Delete as many shards as there are parity shards, spread over data and parity.
+    let step = usize::max(1, rs.total_shards() / usize::max(1, parity_shards));
+    for i in (0..rs.total_shards()).step_by(step).take(parity_shards) {
+        fs::remove_file(&paths[i])?;
+        println!("deleted {}", paths[i].display());
+    }
+
+    let mut received: Vec<Option<Vec<u8>>> = paths.iter().map(|path| fs::read(path).ok()).collect();
+    rs.reconstruct_erasures(&mut received)
+        .map_err(|e| format!("reconstruction failed: {e}"))?;
+
+    let mut recovered: Vec<u8> = received
+        .into_iter()
+        .take(data_shards)
+        .flat_map(|shard| shard.unwrap_or_default())
+        .collect();
+    recovered.truncate(original.len());
+
+    if recovered == original {
+        println!(
+            "recovered {} bytes, identical to the original",
+            recovered.len()
+        );
+        Ok(())
+    } else {
+        Err("the recovered file differs from the original".into())
+    }
+}
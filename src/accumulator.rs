@@ -0,0 +1,104 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Sums of products with a single reduction at the end.
+
+use crate::{extend_bit, GF};
+
+/// Accumulates products of elements without reducing them modulo the AES polynomial, and
+/// reduces the sum only once in [`finish`](ClmulAccumulator::finish). This saves the reduction
+/// steps of every multiplication in long dot products, such as the rows of a matrix product.
+///
+/// The unreduced product of two elements is a carry-less product with a degree of at most 14,
+/// which fits in an `u16`. Products are added with exclusive or, which has no carries, so the
+/// sum never has a higher degree than its terms. Any number of products can therefore be
+/// accumulated without overflowing, and [`finish`](ClmulAccumulator::finish) can't fail.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{ClmulAccumulator, GF};
+/// let mut accumulator = ClmulAccumulator::new();
+/// accumulator.add_product(GF(0x53), GF(0xca));
+/// accumulator.add_product(GF(0x02), GF(0x80));
+///
+/// assert_eq!(accumulator.finish(), GF(0x53) * GF(0xca) + GF(0x02) * GF(0x80));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Default)]
+pub struct ClmulAccumulator {
+    sum: u16,
+}
+
+impl ClmulAccumulator {
+    /// Creates an accumulator with a sum of zero.
+    pub fn new() -> Self {
+        ClmulAccumulator { sum: 0 }
+    }
+
+    /// Adds the unreduced product of `a` and `b` to the sum. Every bit of `b` costs a masked
+    /// shift and exclusive or, so the running time does not depend on the elements.
+    pub fn add_product(&mut self, a: GF, b: GF) {
+        for i in 0..8 {
+            let mask = extend_bit((b.0 >> i) & 1) as u16;
+            self.sum ^= (mask & a.0 as u16) << i;
+        }
+    }
+
+    /// Reduces the sum modulo the AES polynomial.
+    pub fn finish(self) -> GF {
+        let mut sum = self.sum;
+        for i in (8..15).rev() {
+            let mask = 0u16.wrapping_sub((sum >> i) & 1);
+            sum ^= mask & (0x11b << (i - 8));
+        }
+        GF(sum as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_products_match_multiplication() {
+        for a in 0u8..=255u8 {
+            for b in 0u8..=255u8 {
+                let mut accumulator = ClmulAccumulator::new();
+                accumulator.add_product(GF(a), GF(b));
+                assert_eq!(accumulator.finish(), GF(a) * GF(b));
+            }
+        }
+        assert_eq!(ClmulAccumulator::default().finish(), GF(0));
+    }
+
+    #[test]
+    fn long_accumulation_matches_multiplication() {
+        // Far more products than any register width, which would overflow with carries.
+        let mut accumulator = ClmulAccumulator::new();
+        let mut expected = GF(0);
+        for i in 0..100_000u32 {
+            let (a, b) = (GF((i * 7 + 3) as u8), GF((i * 13 + 1) as u8 | 0x80));
+            accumulator.add_product(a, b);
+            expected += a * b;
+        }
+        assert_eq!(accumulator.finish(), expected);
+    }
+}
@@ -0,0 +1,387 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Isochronous building blocks of the AES block cipher, as specified in
+//! [FIPS 197](https://csrc.nist.gov/csrc/media/publications/fips/197/final/documents/fips-197.pdf).
+//!
+//! The state is stored in column-major order, like in FIPS 197 and most other AES
+//! implementations: byte `r + 4 * c` is in row `r` and column `c`. This is the order in which
+//! the bytes of a block are copied into the state. Use [`transpose_state`] to convert a state
+//! that is stored in row-major order.
+
+use crate::GF;
+
+/// The AES S-box, which is the multiplicative inverse in GF(2<sup>8</sup>) followed by an
+/// affine transformation over GF(2). It is calculated instead of looked up in a table.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::aes::aes_sbox;
+/// assert_eq!(aes_sbox(0x53), 0xed);
+/// ```
+pub fn aes_sbox(x: u8) -> u8 {
+    let b = GF(x).multiplicative_inverse().0;
+    b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4) ^ 0x63
+}
+
+/// The inverse of the AES S-box, which is the inverse of the affine transformation followed by
+/// the multiplicative inverse in GF(2<sup>8</sup>).
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::aes::aes_inv_sbox;
+/// assert_eq!(aes_inv_sbox(0xed), 0x53);
+/// ```
+pub fn aes_inv_sbox(x: u8) -> u8 {
+    let b = x.rotate_left(1) ^ x.rotate_left(3) ^ x.rotate_left(6) ^ 0x05;
+    GF(b).multiplicative_inverse().0
+}
+
+/// The SubBytes transformation, which applies [`aes_sbox`] to every byte of the state.
+pub fn aes_sub_bytes(state: &mut [u8; 16]) {
+    for byte in state.iter_mut() {
+        *byte = aes_sbox(*byte);
+    }
+}
+
+/// The InvSubBytes transformation, which applies [`aes_inv_sbox`] to every byte of the state.
+pub fn aes_inv_sub_bytes(state: &mut [u8; 16]) {
+    for byte in state.iter_mut() {
+        *byte = aes_inv_sbox(*byte);
+    }
+}
+
+/// Applies [`aes_sbox`] to every byte of `input`, and writes the results to `output`. Unlike
+/// [`aes_sub_bytes`], the buffers can have any length. The running time only depends on the
+/// length.
+///
+/// # Panics
+/// Panics if `input` and `output` have different lengths.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::aes::aes_sbox_slice;
+/// let mut output = [0; 3];
+/// aes_sbox_slice(&[0x00, 0x01, 0x53], &mut output);
+///
+/// assert_eq!(output, [0x63, 0x7c, 0xed]);
+/// ```
+pub fn aes_sbox_slice(input: &[u8], output: &mut [u8]) {
+    assert_eq!(
+        input.len(),
+        output.len(),
+        "the input and output must have the same length"
+    );
+    for (o, &i) in output.iter_mut().zip(input) {
+        *o = aes_sbox(i);
+    }
+}
+
+/// Converts between the row-major and the column-major order of the state. Transposing twice
+/// gives the original state.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::aes::transpose_state;
+/// let row_major: [u8; 16] = core::array::from_fn(|i| i as u8);
+/// let column_major = transpose_state(&row_major);
+///
+/// // Row 0, column 1
+/// assert_eq!(row_major[1], column_major[4]);
+/// ```
+pub fn transpose_state(state: &[u8; 16]) -> [u8; 16] {
+    let mut transposed = [0; 16];
+    for r in 0..4 {
+        for c in 0..4 {
+            transposed[r + 4 * c] = state[4 * r + c];
+        }
+    }
+    transposed
+}
+
+/// The ShiftRows transformation, which rotates row `r` of the state `r` positions to the left.
+pub fn aes_shift_rows(state: &mut [u8; 16]) {
+    let original = *state;
+    for r in 0..4 {
+        for c in 0..4 {
+            state[r + 4 * c] = original[r + 4 * ((c + r) % 4)];
+        }
+    }
+}
+
+/// The InvShiftRows transformation, which rotates row `r` of the state `r` positions to the
+/// right.
+pub fn aes_inv_shift_rows(state: &mut [u8; 16]) {
+    let original = *state;
+    for r in 0..4 {
+        for c in 0..4 {
+            state[r + 4 * ((c + r) % 4)] = original[r + 4 * c];
+        }
+    }
+}
+
+/// Multiplies every column of the state, as a polynomial over GF(2<sup>8</sup>), by
+/// `coefficients` modulo <i>x</i><sup>4</sup> + 1. Element `i` of `coefficients` is the
+/// coefficient of <i>x</i><sup>i</sup>.
+fn mix_columns(state: &mut [u8; 16], coefficients: [GF; 4]) {
+    for column in state.chunks_exact_mut(4) {
+        let a = [GF(column[0]), GF(column[1]), GF(column[2]), GF(column[3])];
+        for (r, byte) in column.iter_mut().enumerate() {
+            *byte = (0..4)
+                .fold(GF(0), |acc, i| acc + coefficients[i] * a[(r + 4 - i) % 4])
+                .0;
+        }
+    }
+}
+
+/// The MixColumns transformation, which multiplies every column of the state by the polynomial
+/// 3<i>x</i><sup>3</sup> + <i>x</i><sup>2</sup> + <i>x</i> + 2 modulo <i>x</i><sup>4</sup> + 1.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::aes::aes_mix_columns;
+/// let mut state = [0xdb, 0x13, 0x53, 0x45, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0];
+/// aes_mix_columns(&mut state);
+///
+/// assert_eq!(state[..8], [0x8e, 0x4d, 0xa1, 0xbc, 1, 1, 1, 1]);
+/// ```
+pub fn aes_mix_columns(state: &mut [u8; 16]) {
+    mix_columns(state, [GF(0x02), GF(0x01), GF(0x01), GF(0x03)]);
+}
+
+/// The InvMixColumns transformation, which multiplies every column of the state by the
+/// polynomial 11<i>x</i><sup>3</sup> + 13<i>x</i><sup>2</sup> + 9<i>x</i> + 14 modulo
+/// <i>x</i><sup>4</sup> + 1, the inverse of the MixColumns polynomial.
+pub fn aes_inv_mix_columns(state: &mut [u8; 16]) {
+    mix_columns(state, [GF(0x0e), GF(0x09), GF(0x0d), GF(0x0b)]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sbox_examples() {
+        assert_eq!(aes_sbox(0x00), 0x63);
+        assert_eq!(aes_sbox(0x01), 0x7c);
+        assert_eq!(aes_sbox(0x53), 0xed);
+        assert_eq!(aes_sbox(0xff), 0x16);
+    }
+
+    #[test]
+    fn inverse_sbox_inverts_sbox() {
+        for x in 0u8..=255u8 {
+            assert_eq!(aes_inv_sbox(aes_sbox(x)), x);
+        }
+    }
+
+    #[test]
+    fn sbox_slice_matches_sbox() {
+        let input: [u8; 256] = core::array::from_fn(|i| i as u8);
+        let mut output = [0; 256];
+        aes_sbox_slice(&input, &mut output);
+        for (&i, &o) in input.iter().zip(&output) {
+            assert_eq!(o, aes_sbox(i));
+        }
+
+        aes_sbox_slice(&[], &mut []);
+
+        let mut odd = [0; 7];
+        aes_sbox_slice(&input[100..107], &mut odd);
+        assert_eq!(odd[..], output[100..107]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sbox_slice_different_lengths() {
+        aes_sbox_slice(&[1, 2, 3], &mut [0; 2]);
+    }
+
+    #[test]
+    fn sub_bytes_fips197_appendix_b() {
+        // The start of round 1 and the state after SubBytes in the cipher example of FIPS 197
+        // appendix B.
+        let mut state = [
+            0x19, 0x3d, 0xe3, 0xbe, 0xa0, 0xf4, 0xe2, 0x2b, 0x9a, 0xc6, 0x8d, 0x2a, 0xe9, 0xf8,
+            0x48, 0x08,
+        ];
+        let expected = [
+            0xd4, 0x27, 0x11, 0xae, 0xe0, 0xbf, 0x98, 0xf1, 0xb8, 0xb4, 0x5d, 0xe5, 0x1e, 0x41,
+            0x52, 0x30,
+        ];
+
+        let original = state;
+
+        aes_sub_bytes(&mut state);
+        assert_eq!(state, expected);
+
+        aes_inv_sub_bytes(&mut state);
+        assert_eq!(state, original);
+    }
+
+    #[test]
+    fn round_fips197_appendix_b() {
+        // The start of round 1, and the states after ShiftRows and MixColumns, in the cipher
+        // example of FIPS 197 appendix B. All states are in column-major order.
+        let mut state = [
+            0x19, 0x3d, 0xe3, 0xbe, 0xa0, 0xf4, 0xe2, 0x2b, 0x9a, 0xc6, 0x8d, 0x2a, 0xe9, 0xf8,
+            0x48, 0x08,
+        ];
+        let after_shift_rows = [
+            0xd4, 0xbf, 0x5d, 0x30, 0xe0, 0xb4, 0x52, 0xae, 0xb8, 0x41, 0x11, 0xf1, 0x1e, 0x27,
+            0x98, 0xe5,
+        ];
+        let after_mix_columns = [
+            0x04, 0x66, 0x81, 0xe5, 0xe0, 0xcb, 0x19, 0x9a, 0x48, 0xf8, 0xd3, 0x7a, 0x28, 0x06,
+            0x26, 0x4c,
+        ];
+
+        let original = state;
+        aes_sub_bytes(&mut state);
+        aes_shift_rows(&mut state);
+        assert_eq!(state, after_shift_rows);
+        aes_mix_columns(&mut state);
+        assert_eq!(state, after_mix_columns);
+
+        aes_inv_mix_columns(&mut state);
+        assert_eq!(state, after_shift_rows);
+        aes_inv_shift_rows(&mut state);
+        aes_inv_sub_bytes(&mut state);
+        assert_eq!(state, original);
+    }
+
+    #[test]
+    fn row_major_state() {
+        let column_major = [
+            0x19, 0x3d, 0xe3, 0xbe, 0xa0, 0xf4, 0xe2, 0x2b, 0x9a, 0xc6, 0x8d, 0x2a, 0xe9, 0xf8,
+            0x48, 0x08,
+        ];
+        // The first row of the state is 19 a0 9a e9.
+        let row_major = transpose_state(&column_major);
+        assert_eq!(row_major[..4], [0x19, 0xa0, 0x9a, 0xe9]);
+        assert_eq!(transpose_state(&row_major), column_major);
+    }
+
+    /// AddRoundKey, which adds every byte of the round key in GF(2^8).
+    fn add_round_key(state: &mut [u8; 16], key: &[u8; 16]) {
+        for (s, &k) in state.iter_mut().zip(key) {
+            *s = (GF(*s) + GF(k)).0;
+        }
+    }
+
+    /// The AES-128 key expansion of FIPS 197 section 5.2, with the round constants calculated
+    /// as powers of x in GF(2^8).
+    fn expand_key(key: &[u8; 16]) -> [[u8; 16]; 11] {
+        let mut round_keys = [[0; 16]; 11];
+        round_keys[0] = *key;
+        let mut rcon = GF(1);
+
+        for round in 1..11 {
+            let previous = round_keys[round - 1];
+            let mut word = [previous[13], previous[14], previous[15], previous[12]];
+            for byte in &mut word {
+                *byte = aes_sbox(*byte);
+            }
+            word[0] ^= rcon.0;
+            rcon *= GF(2);
+
+            for i in 0..16 {
+                let w = if i < 4 {
+                    word[i]
+                } else {
+                    round_keys[round][i - 4]
+                };
+                round_keys[round][i] = previous[i] ^ w;
+            }
+        }
+
+        round_keys
+    }
+
+    #[test]
+    fn single_round_fips197_appendix_b() {
+        // Round 1 of the cipher example of FIPS 197 appendix B, from the start of the round to
+        // the start of round 2.
+        let mut state = [
+            0x19, 0x3d, 0xe3, 0xbe, 0xa0, 0xf4, 0xe2, 0x2b, 0x9a, 0xc6, 0x8d, 0x2a, 0xe9, 0xf8,
+            0x48, 0x08,
+        ];
+        let round_key = [
+            0xa0, 0xfa, 0xfe, 0x17, 0x88, 0x54, 0x2c, 0xb1, 0x23, 0xa3, 0x39, 0x39, 0x2a, 0x6c,
+            0x76, 0x05,
+        ];
+        let start_of_round_2 = [
+            0xa4, 0x9c, 0x7f, 0xf2, 0x68, 0x9f, 0x35, 0x2b, 0x6b, 0x5b, 0xea, 0x43, 0x02, 0x6a,
+            0x50, 0x49,
+        ];
+
+        aes_sub_bytes(&mut state);
+        aes_shift_rows(&mut state);
+        aes_mix_columns(&mut state);
+        add_round_key(&mut state, &round_key);
+        assert_eq!(state, start_of_round_2);
+    }
+
+    #[test]
+    fn aes128_fips197_appendix_b() {
+        let key = [
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+            0x4f, 0x3c,
+        ];
+        let input = [
+            0x32, 0x43, 0xf6, 0xa8, 0x88, 0x5a, 0x30, 0x8d, 0x31, 0x31, 0x98, 0xa2, 0xe0, 0x37,
+            0x07, 0x34,
+        ];
+        let output = [
+            0x39, 0x25, 0x84, 0x1d, 0x02, 0xdc, 0x09, 0xfb, 0xdc, 0x11, 0x85, 0x97, 0x19, 0x6a,
+            0x0b, 0x32,
+        ];
+        let round_keys = expand_key(&key);
+        assert_eq!(round_keys[1][..4], [0xa0, 0xfa, 0xfe, 0x17]);
+
+        let mut state = input;
+        add_round_key(&mut state, &round_keys[0]);
+        for round_key in &round_keys[1..10] {
+            aes_sub_bytes(&mut state);
+            aes_shift_rows(&mut state);
+            aes_mix_columns(&mut state);
+            add_round_key(&mut state, round_key);
+        }
+        aes_sub_bytes(&mut state);
+        aes_shift_rows(&mut state);
+        add_round_key(&mut state, &round_keys[10]);
+        assert_eq!(state, output);
+
+        // The inverse cipher of FIPS 197 section 5.3.
+        add_round_key(&mut state, &round_keys[10]);
+        for round_key in round_keys[1..10].iter().rev() {
+            aes_inv_shift_rows(&mut state);
+            aes_inv_sub_bytes(&mut state);
+            add_round_key(&mut state, round_key);
+            aes_inv_mix_columns(&mut state);
+        }
+        aes_inv_shift_rows(&mut state);
+        aes_inv_sub_bytes(&mut state);
+        add_round_key(&mut state, &round_keys[0]);
+        assert_eq!(state, input);
+    }
+}
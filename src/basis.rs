@@ -0,0 +1,281 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Other bases of GF(2<sup>8</sup>) over GF(2).
+//!
+//! The byte representation of [`GF`] uses the polynomial basis 1, <i>x</i>,
+//! <i>x</i><sup>2</sup>, ..., <i>x</i><sup>7</sup>, where bit `i` is the coordinate of
+//! <i>x</i><sup>i</sup>.
+//!
+//! # Dual basis
+//! The dual basis <i>d</i><sub>0</sub>, ..., <i>d</i><sub>7</sub> of the polynomial basis is the
+//! basis for which the trace pairing Tr(<i>x</i><sup>i</sup> <i>d</i><sub>j</sub>) is 1 if
+//! `i == j` and 0 otherwise, see [`DUAL_BASIS`]. Because of this, coordinate `j` of an element
+//! <i>a</i> in the dual basis is simply Tr(<i>a</i> <i>x</i><sup>j</sup>), which is what makes the
+//! dual basis useful for bit-serial multipliers. The trace of a product <i>ab</i> is the inner
+//! product over GF(2) of the polynomial basis coordinates of <i>a</i> and the dual basis
+//! coordinates of <i>b</i>.
+//!
+//! # Normal basis
+//! A normal basis consists of the conjugates &beta;, &beta;<sup>2</sup>, &beta;<sup>4</sup>,
+//! ..., &beta;<sup>128</sup> of a normal element &beta;, see [`NORMAL_BASIS`]. Squaring an
+//! element permutes the conjugates, so in a normal basis squaring is a rotation of the bits.
+//! [`NormalGF`] stores elements in this basis.
+
+use crate::{extend_bit, GF};
+use core::ops::{Add, AddAssign, Mul, MulAssign};
+
+/// The dual basis of the polynomial basis with respect to the trace. Element `j` is
+/// <i>d</i><sub>j</sub>, the unique element for which Tr(<i>x</i><sup>i</sup>
+/// <i>d</i><sub>j</sub>) is 1 if `i == j` and 0 otherwise.
+pub const DUAL_BASIS: [GF; 8] = [
+    GF(0x29),
+    GF(0xb0),
+    GF(0x58),
+    GF(0x05),
+    GF(0xa6),
+    GF(0x53),
+    GF(0xa4),
+    GF(0x52),
+];
+
+/// Converts an element to its coordinates in the dual basis, where bit `j` is the coordinate of
+/// [`DUAL_BASIS`]`[j]`.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, basis::{from_dual_basis, to_dual_basis, DUAL_BASIS}};
+/// assert_eq!(to_dual_basis(DUAL_BASIS[3]), 0b0000_1000);
+/// assert_eq!(from_dual_basis(to_dual_basis(GF(0x53))), GF(0x53));
+/// ```
+pub fn to_dual_basis(a: GF) -> u8 {
+    let mut coordinates = 0;
+    let mut power = a;
+
+    for j in 0..8 {
+        coordinates |= power.trace() << j;
+        power *= GF(2);
+    }
+
+    coordinates
+}
+
+/// Converts the coordinates in the dual basis back to an element, where bit `j` is the
+/// coordinate of [`DUAL_BASIS`]`[j]`.
+pub fn from_dual_basis(coordinates: u8) -> GF {
+    let mut a = GF(0);
+
+    for (j, &d) in DUAL_BASIS.iter().enumerate() {
+        a += GF(extend_bit((coordinates >> j) & 1) & d.0);
+    }
+
+    a
+}
+
+/// The normal basis that is used by [`NormalGF`]. Element `i` is
+/// &beta;<sup>2<sup>i</sup></sup>, where the normal element &beta; is <i>x</i><sup>5</sup>
+/// (`GF(0x20)`), the smallest element whose conjugates are linearly independent.
+pub const NORMAL_BASIS: [GF; 8] = [
+    GF(0x20),
+    GF(0x6c),
+    GF(0x97),
+    GF(0x94),
+    GF(0x91),
+    GF(0x80),
+    GF(0x9a),
+    GF(0xc5),
+];
+
+/// The normal basis coordinates of the polynomial basis. Element `k` contains the coordinates of
+/// <i>x</i><sup>k</sup>.
+const TO_NORMAL: [u8; 8] = [0xff, 0xf3, 0xe7, 0x5c, 0xcf, 0x01, 0xb8, 0x20];
+
+/// The multiplication matrix of the normal basis. Bit `j` of row `i` is the coordinate of &beta;
+/// in the product &beta;<sup>2<sup>i</sup></sup> &beta;<sup>2<sup>j</sup></sup>. The other
+/// coordinates of the product follow by rotating, since squaring is a rotation.
+const MULTIPLICATION_MATRIX: [u8; 8] = [0xa8, 0xb4, 0x82, 0xb1, 0x6a, 0x1b, 0x90, 0xcf];
+
+/// An element of GF(2<sup>8</sup>) in the normal basis [`NORMAL_BASIS`], where bit `i` is the
+/// coordinate of &beta;<sup>2<sup>i</sup></sup>.
+///
+/// Squaring is a rotation of the bits by one position and multiplication uses the
+/// multiplication matrix of the basis, which makes the representation convenient for hardware
+/// and masked implementations. Just like with [`GF`], all operations are isochronous.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, basis::NormalGF};
+/// let a = NormalGF::from(GF(0x53));
+/// let b = NormalGF::from(GF(0xca));
+///
+/// assert_eq!(GF::from(a * b), GF(0x53) * GF(0xca));
+/// assert_eq!(a.square(), a * a);
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[repr(transparent)]
+pub struct NormalGF(pub u8);
+
+impl NormalGF {
+    /// Calculates the square of the element, which is a rotation of the coordinates.
+    pub fn square(self) -> Self {
+        NormalGF(self.0.rotate_left(1))
+    }
+}
+
+impl From<GF> for NormalGF {
+    fn from(a: GF) -> Self {
+        let mut coordinates = 0;
+
+        for (k, &n) in TO_NORMAL.iter().enumerate() {
+            coordinates ^= extend_bit((a.0 >> k) & 1) & n;
+        }
+
+        NormalGF(coordinates)
+    }
+}
+
+impl From<NormalGF> for GF {
+    fn from(a: NormalGF) -> Self {
+        let mut element = GF(0);
+
+        for (i, &b) in NORMAL_BASIS.iter().enumerate() {
+            element += GF(extend_bit((a.0 >> i) & 1) & b.0);
+        }
+
+        element
+    }
+}
+
+/// Addition is an exclusive or (XOR) of the coordinates, just like in the polynomial basis.
+impl Add for NormalGF {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        NormalGF(self.0 ^ rhs.0)
+    }
+}
+
+impl AddAssign for NormalGF {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs)
+    }
+}
+
+/// Multiplication with the multiplication matrix. Coordinate `k` of the product is the bilinear
+/// form of the matrix applied to both operands rotated by `k` positions.
+impl Mul for NormalGF {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut product = 0;
+
+        for k in 0..8 {
+            let a = self.0.rotate_right(k);
+            let b = rhs.0.rotate_right(k);
+
+            let mut coordinate = 0;
+            for (i, &row) in MULTIPLICATION_MATRIX.iter().enumerate() {
+                coordinate ^= (a >> i) & GF(row & b).parity();
+            }
+            product |= (coordinate & 1) << k;
+        }
+
+        NormalGF(product)
+    }
+}
+
+impl MulAssign for NormalGF {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.mul(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_basis_is_conjugates() {
+        let beta = NORMAL_BASIS[0];
+        for (i, &b) in NORMAL_BASIS.iter().enumerate() {
+            assert_eq!(b, beta.pow(1 << i));
+            assert_eq!(NormalGF::from(b), NormalGF(1 << i));
+        }
+    }
+
+    #[test]
+    fn normal_basis_round_trip() {
+        for a in 0u8..=255u8 {
+            assert_eq!(GF::from(NormalGF::from(GF(a))), GF(a));
+            assert_eq!(NormalGF::from(GF::from(NormalGF(a))), NormalGF(a));
+        }
+    }
+
+    #[test]
+    fn normal_multiplication_matches_gf() {
+        for a in 0u8..=255u8 {
+            for b in 0u8..=255u8 {
+                let product = NormalGF::from(GF(a)) * NormalGF::from(GF(b));
+                assert_eq!(GF::from(product), GF(a) * GF(b));
+            }
+        }
+    }
+
+    #[test]
+    fn squaring_is_rotation() {
+        for a in 0u8..=255u8 {
+            let normal = NormalGF::from(GF(a));
+            assert_eq!(normal * normal, NormalGF(normal.0.rotate_left(1)));
+            assert_eq!(GF::from(normal.square()), GF(a) * GF(a));
+        }
+        assert_eq!(NormalGF::from(GF(1)), NormalGF(0xff));
+    }
+
+    #[test]
+    fn dual_basis_is_dual() {
+        for i in 0..8 {
+            for (j, &d) in DUAL_BASIS.iter().enumerate() {
+                let expected = (i == j) as u8;
+                assert_eq!((GF(1 << i) * d).trace(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn dual_basis_round_trip() {
+        for a in 0u8..=255u8 {
+            assert_eq!(from_dual_basis(to_dual_basis(GF(a))), GF(a));
+            assert_eq!(to_dual_basis(from_dual_basis(a)), a);
+        }
+    }
+
+    #[test]
+    fn trace_is_inner_product() {
+        for a in 0u8..=255u8 {
+            for b in [0x00, 0x01, 0x02, 0x13, 0x57, 0x83, 0xca, 0xff] {
+                let inner = GF(a & to_dual_basis(GF(b))).parity();
+                assert_eq!((GF(a) * GF(b)).trace(), inner);
+            }
+        }
+    }
+}
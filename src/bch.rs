@@ -0,0 +1,209 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Binary BCH codes, which are the subfield subcodes of Reed-Solomon codes over
+//! GF(2<sup>8</sup>).
+
+use crate::{GFError, GFPoly, GF};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// The length of a primitive BCH code with roots in GF(2<sup>8</sup>).
+const LENGTH: usize = 255;
+
+/// A narrow-sense binary BCH code of length 255 with a given designed distance.
+///
+/// The generator polynomial <i>g</i>(<i>x</i>) is the least common multiple of the minimal
+/// polynomials of &alpha;<sup>1</sup>, ..., &alpha;<sup>&delta; - 1</sup>, where &alpha; is
+/// [`GF::GENERATOR`] and &delta; is the designed distance, see [`GFPoly::minimal_polynomial`].
+/// It has its coefficients in GF(2), so the codewords are bits, which are stored as `GF(0)` and
+/// `GF(1)`. The minimum distance of the code is at least &delta;, so it corrects
+/// (&delta; - 1) / 2 errors.
+///
+/// Like for [`ReedSolomon`](crate::ReedSolomon), a codeword consists of the data bits followed
+/// by the parity bits, and its first bit is the coefficient of the highest degree.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, bch::BchCode};
+/// // The (255, 239) code, which corrects two errors.
+/// let code = BchCode::new(5).unwrap();
+/// assert_eq!(code.data_bits(), 239);
+///
+/// let message: Vec<GF> = (0..239).map(|i| GF(i as u8 % 3 % 2)).collect();
+/// let codeword = code.encode(&message).unwrap();
+/// assert_eq!(codeword[..239], message[..]);
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct BchCode {
+    designed_distance: usize,
+    generator: GFPoly,
+}
+
+impl BchCode {
+    /// Creates the code with the given designed distance.
+    ///
+    /// Returns [`GFError::OutOfRange`] if the designed distance is not between 1 and 255.
+    pub fn new(designed_distance: usize) -> Result<Self, GFError> {
+        if !(1..=LENGTH).contains(&designed_distance) {
+            return Err(GFError::OutOfRange);
+        }
+
+        // The minimal polynomials are irreducible, so a minimal polynomial that is already a
+        // factor of the generator has no common factor with it.
+        let generator = (1..designed_distance).fold(GFPoly::monomial(GF(1), 0), |acc, i| {
+            let minimal = GFPoly::minimal_polynomial(GF::GENERATOR.pow(i as u32));
+            let common = acc.gcd(&minimal);
+            (&acc * &minimal).div_rem(&common).0
+        });
+
+        Ok(BchCode {
+            designed_distance,
+            generator,
+        })
+    }
+
+    /// The designed distance, which is a lower bound on the minimum distance.
+    pub fn designed_distance(&self) -> usize {
+        self.designed_distance
+    }
+
+    /// The generator polynomial, which has its coefficients in GF(2).
+    pub fn generator_polynomial(&self) -> &GFPoly {
+        &self.generator
+    }
+
+    /// The number of data bits per codeword.
+    pub fn data_bits(&self) -> usize {
+        LENGTH - self.parity_bits()
+    }
+
+    /// The number of parity bits per codeword, which is the degree of the generator polynomial.
+    pub fn parity_bits(&self) -> usize {
+        self.generator.degree().unwrap_or(0)
+    }
+
+    /// The number of bits per codeword.
+    pub fn total_bits(&self) -> usize {
+        LENGTH
+    }
+
+    /// Encodes the data bits into a codeword, which consists of the data followed by the parity
+    /// bits. The parity bits are the remainder of the data polynomial, multiplied by
+    /// <i>x</i><sup>n - k</sup>, divided by the generator polynomial.
+    ///
+    /// Returns an error if
+    /// * the number of data bits is wrong ([`GFError::DimensionMismatch`]),
+    /// * a data symbol is not `GF(0)` or `GF(1)` ([`GFError::OutOfRange`]).
+    pub fn encode(&self, data: &[GF]) -> Result<Vec<GF>, GFError> {
+        if data.len() != self.data_bits() {
+            return Err(GFError::DimensionMismatch);
+        }
+        if data.iter().any(|&bit| bit != GF(0) && bit != GF(1)) {
+            return Err(GFError::OutOfRange);
+        }
+
+        let parity = self.parity_bits();
+        let mut shifted = vec![GF(0); parity];
+        shifted.extend(data.iter().rev());
+        let remainder = GFPoly::new(shifted).div_rem(&self.generator).1;
+
+        let mut codeword = data.to_vec();
+        codeword.extend((0..parity).rev().map(|degree| {
+            remainder
+                .coefficients()
+                .get(degree)
+                .copied()
+                .unwrap_or(GF(0))
+        }));
+        Ok(codeword)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polynomial(codeword: &[GF]) -> GFPoly {
+        GFPoly::new(codeword.iter().rev().copied().collect())
+    }
+
+    #[test]
+    fn known_dimensions() {
+        for (designed_distance, data_bits) in [(1, 255), (3, 247), (5, 239), (7, 231), (255, 1)] {
+            let code = BchCode::new(designed_distance).unwrap();
+            assert_eq!(code.data_bits(), data_bits);
+            assert_eq!(code.total_bits(), 255);
+        }
+
+        // The roots of the minimal polynomial of a^2 are also roots of that of a, so an even
+        // designed distance gives the same code as the next odd one.
+        assert_eq!(
+            BchCode::new(4).unwrap().generator_polynomial(),
+            BchCode::new(5).unwrap().generator_polynomial()
+        );
+    }
+
+    #[test]
+    fn generator_has_consecutive_roots() {
+        let code = BchCode::new(9).unwrap();
+        let generator = code.generator_polynomial();
+
+        assert!(generator
+            .coefficients()
+            .iter()
+            .all(|&c| c == GF(0) || c == GF(1)));
+        for i in 1..9 {
+            assert_eq!(generator.eval(GF::GENERATOR.pow(i)), GF(0));
+        }
+        assert_ne!(generator.eval(GF(1)), GF(0));
+    }
+
+    #[test]
+    fn codewords_are_multiples_of_the_generator() {
+        let code = BchCode::new(7).unwrap();
+        for seed in 0..4 {
+            let data: Vec<GF> = (0..code.data_bits())
+                .map(|i| GF(((i * 7 + seed) % 5 % 2) as u8))
+                .collect();
+            let codeword = code.encode(&data).unwrap();
+
+            assert_eq!(codeword.len(), 255);
+            assert_eq!(codeword[..code.data_bits()], data[..]);
+            assert!(codeword.iter().all(|&c| c == GF(0) || c == GF(1)));
+            let (_, remainder) = polynomial(&codeword).div_rem(code.generator_polynomial());
+            assert!(remainder.is_zero());
+        }
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(BchCode::new(0), Err(GFError::OutOfRange));
+        assert_eq!(BchCode::new(256), Err(GFError::OutOfRange));
+
+        let code = BchCode::new(3).unwrap();
+        assert_eq!(code.encode(&[GF(1); 246]), Err(GFError::DimensionMismatch));
+        let mut data = vec![GF(0); 247];
+        data[10] = GF(2);
+        assert_eq!(code.encode(&data), Err(GFError::OutOfRange));
+    }
+}
@@ -0,0 +1,145 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! A model of a bit-serial multiplier, which processes one bit of an operand per clock cycle.
+
+use crate::{extend_bit, GF};
+
+/// A multiplication in GF(2<sup>8</sup>) that is performed one bit at a time.
+///
+/// Every [`step`](BitSerialMul::step) is one iteration of the shift-and-reduce loop of the `*`
+/// operator of [`GF`]: the shifted multiplicand is added to the partial product if the current
+/// bit of the multiplier is set, after which the multiplicand is multiplied by <i>x</i> and
+/// reduced. The registers can be inspected between the steps. After 8 steps, the partial
+/// product is the product.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, bit_serial::BitSerialMul};
+/// let mut multiplier = BitSerialMul::new(GF(0x53), GF(0xca));
+/// while multiplier.step() {}
+///
+/// assert_eq!(multiplier.result(), Some(GF(0x01)));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct BitSerialMul {
+    multiplicand: u8,
+    multiplier: u8,
+    partial_product: u8,
+    steps: u8,
+}
+
+impl BitSerialMul {
+    /// Starts the multiplication of `a` and `b`. The bits of `b` are processed from the rightmost
+    /// to the leftmost bit.
+    pub fn new(a: GF, b: GF) -> Self {
+        BitSerialMul {
+            multiplicand: a.0,
+            multiplier: b.0,
+            partial_product: 0,
+            steps: 0,
+        }
+    }
+
+    /// Performs a single iteration of the loop. Returns `false` without doing anything if all 8
+    /// steps have already been performed, and `true` otherwise.
+    pub fn step(&mut self) -> bool {
+        if self.steps == 8 {
+            return false;
+        }
+
+        self.partial_product ^= extend_bit(self.multiplier & 1) & self.multiplicand;
+        self.multiplier >>= 1;
+
+        let carry = (self.multiplicand >> 7) & 1;
+        self.multiplicand <<= 1;
+        self.multiplicand ^= extend_bit(carry) & 0x1b;
+
+        self.steps += 1;
+        true
+    }
+
+    /// The number of steps that have been performed.
+    pub fn steps(&self) -> u8 {
+        self.steps
+    }
+
+    /// The multiplicand, which has been multiplied by <i>x</i> once for every step.
+    pub fn multiplicand(&self) -> GF {
+        GF(self.multiplicand)
+    }
+
+    /// The product of the multiplicand and the bits of the multiplier that have been processed.
+    pub fn partial_product(&self) -> GF {
+        GF(self.partial_product)
+    }
+
+    /// The product, which is only available after 8 steps.
+    pub fn result(&self) -> Option<GF> {
+        if self.steps == 8 {
+            Some(GF(self.partial_product))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eight_steps_give_product() {
+        for (a, b) in [
+            (0x53, 0xca),
+            (0x57, 0x83),
+            (0x00, 0xff),
+            (0x80, 0x02),
+            (0xff, 0xff),
+        ] {
+            let mut multiplier = BitSerialMul::new(GF(a), GF(b));
+            for step in 0..8 {
+                assert_eq!(multiplier.result(), None);
+                assert_eq!(multiplier.steps(), step);
+                assert!(multiplier.step());
+            }
+
+            assert_eq!(multiplier.result(), Some(GF(a) * GF(b)));
+            assert!(!multiplier.step());
+            assert_eq!(multiplier.result(), Some(GF(a) * GF(b)));
+        }
+    }
+
+    #[test]
+    fn registers_after_each_step() {
+        let mut multiplier = BitSerialMul::new(GF(0x57), GF(0x83));
+        for step in 1..=8u32 {
+            multiplier.step();
+
+            // The multiplicand is multiplied by x in every step, and the partial product
+            // contains the bits of the multiplier that have been processed.
+            let processed = GF((0x83u32 & ((1 << step) - 1)) as u8);
+            assert_eq!(multiplier.multiplicand(), GF(0x57) * GF(2).pow(step));
+            assert_eq!(multiplier.partial_product(), GF(0x57) * processed);
+        }
+    }
+}
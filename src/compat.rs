@@ -0,0 +1,79 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Free functions on raw bytes, with the names that other GF(2<sup>8</sup>) crates use, to make
+//! porting code to this crate easier.
+//!
+//! This module is only available with the `compat` feature. Every function delegates to the
+//! isochronous operators of [`GF`]. New code should use [`GF`] directly.
+
+use crate::GF;
+
+/// Adds two elements, which is `(GF(a) + GF(b)).0`.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::compat::gf_add;
+/// assert_eq!(gf_add(0x53, 0xca), 0x99);
+/// ```
+pub fn gf_add(a: u8, b: u8) -> u8 {
+    (GF(a) + GF(b)).0
+}
+
+/// Multiplies two elements, which is `(GF(a) * GF(b)).0`.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::compat::gf_mul;
+/// assert_eq!(gf_mul(0x53, 0xca), 0x01);
+/// ```
+pub fn gf_mul(a: u8, b: u8) -> u8 {
+    (GF(a) * GF(b)).0
+}
+
+/// The multiplicative inverse, which is `GF(a).multiplicative_inverse().0`. The inverse of 0
+/// is 0.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::compat::gf_inv;
+/// assert_eq!(gf_inv(0x53), 0xca);
+/// ```
+pub fn gf_inv(a: u8) -> u8 {
+    GF(a).multiplicative_inverse().0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn functions_match_operators() {
+        for a in 0u8..=255u8 {
+            assert_eq!(gf_inv(a), GF(a).multiplicative_inverse().0);
+            for b in (0u8..=255u8).step_by(5) {
+                assert_eq!(gf_add(a, b), (GF(a) + GF(b)).0);
+                assert_eq!(gf_mul(a, b), (GF(a) * GF(b)).0);
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Counting of field operations, to analyse how many multiplications and inverses an
+//! algorithm needs.
+//!
+//! This module is only available with the `counting` feature, which requires the standard
+//! library for its thread-local counters.
+
+use crate::GF;
+use core::cell::Cell;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+std::thread_local! {
+    static MULTIPLICATIONS: Cell<usize> = const { Cell::new(0) };
+    static INVERSES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// The number of operations that were counted on the current thread.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Default)]
+pub struct OperationCounts {
+    /// The number of multiplications.
+    pub multiplications: usize,
+    /// The number of multiplicative inverses, including the ones needed for divisions.
+    pub inverses: usize,
+}
+
+/// The operations that were counted on the current thread since the last [`reset_counts`].
+pub fn counts() -> OperationCounts {
+    OperationCounts {
+        multiplications: MULTIPLICATIONS.with(Cell::get),
+        inverses: INVERSES.with(Cell::get),
+    }
+}
+
+/// Resets the counters of the current thread to zero.
+pub fn reset_counts() {
+    MULTIPLICATIONS.with(|c| c.set(0));
+    INVERSES.with(|c| c.set(0));
+}
+
+fn increment(counter: &'static std::thread::LocalKey<Cell<usize>>) {
+    counter.with(|c| c.set(c.get() + 1));
+}
+
+/// Wrapper around [`GF`] that counts every multiplication and every multiplicative inverse in
+/// thread-local counters. Additions and subtractions are not counted.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, counting::{counts, reset_counts, CountingGF}};
+/// reset_counts();
+/// let x = CountingGF(GF(3)) * CountingGF(GF(7)) + CountingGF(GF(1));
+///
+/// assert_eq!(x.0, GF(3) * GF(7) + GF(1));
+/// assert_eq!(counts().multiplications, 1);
+/// ```
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug, Hash)]
+pub struct CountingGF(pub GF);
+
+impl CountingGF {
+    /// Calculates the multiplicative inverse with [`GF::multiplicative_inverse`], and counts
+    /// it.
+    pub fn multiplicative_inverse(self) -> Self {
+        increment(&INVERSES);
+        CountingGF(self.0.multiplicative_inverse())
+    }
+}
+
+impl From<GF> for CountingGF {
+    fn from(x: GF) -> Self {
+        CountingGF(x)
+    }
+}
+
+impl From<u8> for CountingGF {
+    fn from(x: u8) -> Self {
+        CountingGF(GF(x))
+    }
+}
+
+impl Add for CountingGF {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        CountingGF(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for CountingGF {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs)
+    }
+}
+
+impl Sub for CountingGF {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        CountingGF(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for CountingGF {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.sub(rhs)
+    }
+}
+
+impl Mul for CountingGF {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        increment(&MULTIPLICATIONS);
+        CountingGF(self.0 * rhs.0)
+    }
+}
+
+impl MulAssign for CountingGF {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.mul(rhs)
+    }
+}
+
+/// Division is counted as a multiplicative inverse and a multiplication.
+impl Div for CountingGF {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        self.mul(rhs.multiplicative_inverse())
+    }
+}
+
+impl DivAssign for CountingGF {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = self.div(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matrix_multiplication_count() {
+        let a = [[CountingGF::from(0x57); 4]; 3];
+        let b = [[CountingGF::from(0x83); 2]; 4];
+
+        reset_counts();
+        let mut product = [[CountingGF::from(0); 2]; 3];
+        for (i, row) in product.iter_mut().enumerate() {
+            for (j, element) in row.iter_mut().enumerate() {
+                for k in 0..4 {
+                    *element += a[i][k] * b[k][j];
+                }
+            }
+        }
+
+        // A 3x4 by 4x2 product needs 3 * 4 * 2 multiplications.
+        assert_eq!(
+            counts(),
+            OperationCounts {
+                multiplications: 24,
+                inverses: 0
+            }
+        );
+        assert_eq!(product[2][1].0, GF(0));
+    }
+
+    #[test]
+    fn division_counts_inverse_and_multiplication() {
+        reset_counts();
+        let mut x = CountingGF::from(0x57);
+        x /= CountingGF::from(0x83);
+        x = x.multiplicative_inverse();
+
+        assert_eq!(x.0, GF(0x83) / GF(0x57));
+        assert_eq!(
+            counts(),
+            OperationCounts {
+                multiplications: 1,
+                inverses: 2
+            }
+        );
+
+        reset_counts();
+        assert_eq!(counts(), OperationCounts::default());
+    }
+}
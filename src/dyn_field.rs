@@ -0,0 +1,202 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Fields that are chosen at runtime.
+//!
+//! [`DynField`] can be used as a trait object, so generic code can work with a field whose size
+//! is only known at runtime, without being monomorphized for every field. Elements are passed
+//! as `u64`, of which only the lowest [`DynField::bits`] bits are used.
+
+use crate::{GFn, GF, GF32};
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+
+/// The operations of a field of characteristic 2, with the elements represented as `u64`.
+///
+/// Bits above [`DynField::bits`] are ignored by all operations, and are zero in all results.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::dyn_field::{field_with_bits, DynField};
+/// let field: Box<dyn DynField> = field_with_bits(8).unwrap();
+///
+/// assert_eq!(field.mul(0x53, 0xca), 0x01);
+/// assert_eq!(field.inverse(0x53), 0xca);
+/// ```
+pub trait DynField {
+    /// The number of bits of an element, so the field has 2<sup>bits</sup> elements.
+    fn bits(&self) -> u32;
+
+    /// Adds two elements.
+    fn add(&self, a: u64, b: u64) -> u64;
+
+    /// Multiplies two elements.
+    fn mul(&self, a: u64, b: u64) -> u64;
+
+    /// Calculates the multiplicative inverse, where the inverse of zero is zero.
+    fn inverse(&self, a: u64) -> u64;
+
+    /// Subtracts two elements, which is the same as adding them in characteristic 2.
+    fn sub(&self, a: u64, b: u64) -> u64 {
+        self.add(a, b)
+    }
+
+    /// Divides `a` by `b`, where division by zero gives zero.
+    fn div(&self, a: u64, b: u64) -> u64 {
+        self.mul(a, self.inverse(b))
+    }
+}
+
+/// Implements [`DynField`] for the field with elements of type `T`.
+///
+/// This is a marker without any data, so `Box::new(Field::<GF>::new())` doesn't allocate.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Default)]
+pub struct Field<T> {
+    element: PhantomData<T>,
+}
+
+impl<T> Field<T> {
+    /// Creates the marker for the field with elements of type `T`.
+    pub const fn new() -> Self {
+        Field {
+            element: PhantomData,
+        }
+    }
+}
+
+impl DynField for Field<GF> {
+    fn bits(&self) -> u32 {
+        8
+    }
+
+    fn add(&self, a: u64, b: u64) -> u64 {
+        (GF(a as u8) + GF(b as u8)).0 as u64
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        (GF(a as u8) * GF(b as u8)).0 as u64
+    }
+
+    fn inverse(&self, a: u64) -> u64 {
+        GF(a as u8).multiplicative_inverse().0 as u64
+    }
+}
+
+impl<const POLY: u16> DynField for Field<GFn<POLY>> {
+    fn bits(&self) -> u32 {
+        8
+    }
+
+    fn add(&self, a: u64, b: u64) -> u64 {
+        (GFn::<POLY>(a as u8) + GFn(b as u8)).0 as u64
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        (GFn::<POLY>(a as u8) * GFn(b as u8)).0 as u64
+    }
+
+    fn inverse(&self, a: u64) -> u64 {
+        GFn::<POLY>(a as u8).multiplicative_inverse().0 as u64
+    }
+}
+
+impl DynField for Field<GF32> {
+    fn bits(&self) -> u32 {
+        32
+    }
+
+    fn add(&self, a: u64, b: u64) -> u64 {
+        (GF32(a as u32) + GF32(b as u32)).0 as u64
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        (GF32(a as u32) * GF32(b as u32)).0 as u64
+    }
+
+    fn inverse(&self, a: u64) -> u64 {
+        GF32(a as u32).multiplicative_inverse().0 as u64
+    }
+}
+
+/// The field of this crate with elements of `bits` bits: [`GF`] for 8 bits and [`GF32`] for 32
+/// bits. Returns `None` for other sizes.
+pub fn field_with_bits(bits: u32) -> Option<Box<dyn DynField>> {
+    match bits {
+        8 => Some(Box::new(Field::<GF>::new())),
+        32 => Some(Box::new(Field::<GF32>::new())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Checks the field axioms on a few elements, only through the trait object.
+    fn check_field(field: &dyn DynField, elements: &[u64]) {
+        for &a in elements {
+            assert_eq!(field.add(a, a), 0);
+            assert_eq!(field.mul(a, 1), a);
+            if a != 0 {
+                assert_eq!(field.mul(a, field.inverse(a)), 1);
+                assert_eq!(field.div(a, a), 1);
+            }
+            for &b in elements {
+                assert_eq!(field.mul(a, b), field.mul(b, a));
+                assert_eq!(field.sub(field.add(a, b), b), a);
+            }
+        }
+        assert_eq!(field.inverse(0), 0);
+    }
+
+    #[test]
+    fn operations_through_trait_objects() {
+        let fields: Vec<Box<dyn DynField>> = [8, 32]
+            .iter()
+            .map(|&bits| field_with_bits(bits).unwrap())
+            .collect();
+
+        check_field(fields[0].as_ref(), &[0, 1, 2, 0x53, 0xca, 0xff]);
+        assert_eq!(fields[0].mul(0x53, 0xca), 1);
+
+        check_field(
+            fields[1].as_ref(),
+            &[0, 1, 2, 0x53, 0x8000_0000, 0xffff_ffff],
+        );
+        // x^31 * x == x^32 == x^7 + x^3 + x^2 + 1
+        assert_eq!(fields[1].mul(0x8000_0000, 2), 0x8d);
+        assert_eq!(fields[1].bits(), 32);
+
+        assert!(field_with_bits(16).is_none());
+    }
+
+    #[test]
+    fn results_stay_in_range() {
+        let field = Field::<GF>::new();
+        assert_eq!(field.add(0x1_0053, 0xca), 0x99);
+        assert_eq!(field.mul(0xff00, 0x53), 0);
+
+        let field: &dyn DynField = &Field::<GFn<0x11d>>::new();
+        assert_eq!(field.mul(2, 0x80), 0x1d);
+    }
+}
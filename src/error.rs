@@ -0,0 +1,116 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! The error type of the fallible operations in this crate.
+
+use core::fmt;
+
+/// Errors that can occur in the fallible operations of this crate.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, GFError};
+/// assert_eq!(GF(83).checked_div(GF(0)), Err(GFError::DivisionByZero));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+#[non_exhaustive]
+pub enum GFError {
+    /// An element was divided by `GF(0)`, which has no multiplicative inverse.
+    DivisionByZero,
+    /// A matrix that had to be inverted is singular.
+    SingularMatrix,
+    /// The dimensions or lengths of the operands don't match.
+    DimensionMismatch,
+    /// A parameter is outside of its valid range.
+    OutOfRange,
+    /// Not enough shares were given to reconstruct a secret.
+    TooFewShares,
+    /// The same evaluation point was given more than once.
+    DuplicatePoint,
+    /// A codeword contains more errors than can be corrected.
+    TooManyErrors,
+    /// A string could not be parsed.
+    InvalidFormat,
+}
+
+impl fmt::Display for GFError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            GFError::DivisionByZero => "division by zero",
+            GFError::SingularMatrix => "matrix is singular",
+            GFError::DimensionMismatch => "dimensions don't match",
+            GFError::OutOfRange => "parameter out of range",
+            GFError::TooFewShares => "too few shares",
+            GFError::DuplicatePoint => "duplicate evaluation point",
+            GFError::TooManyErrors => "too many errors to correct",
+            GFError::InvalidFormat => "invalid format",
+        };
+        f.write_str(message)
+    }
+}
+
+impl core::error::Error for GFError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GF;
+
+    #[test]
+    fn display_variants() {
+        let cases = [
+            (GFError::DivisionByZero, "division by zero"),
+            (GFError::SingularMatrix, "matrix is singular"),
+            (GFError::DimensionMismatch, "dimensions don't match"),
+            (GFError::OutOfRange, "parameter out of range"),
+            (GFError::TooFewShares, "too few shares"),
+            (GFError::DuplicatePoint, "duplicate evaluation point"),
+            (GFError::TooManyErrors, "too many errors to correct"),
+            (GFError::InvalidFormat, "invalid format"),
+        ];
+
+        for (error, message) in cases {
+            // Format into a fixed buffer, since there is no allocator in core.
+            struct Buffer([u8; 32], usize);
+            impl fmt::Write for Buffer {
+                fn write_str(&mut self, s: &str) -> fmt::Result {
+                    self.0[self.1..self.1 + s.len()].copy_from_slice(s.as_bytes());
+                    self.1 += s.len();
+                    Ok(())
+                }
+            }
+
+            let mut buffer = Buffer([0; 32], 0);
+            fmt::write(&mut buffer, format_args!("{}", error)).unwrap();
+            assert_eq!(&buffer.0[..buffer.1], message.as_bytes());
+        }
+    }
+
+    #[test]
+    fn checked_div_by_zero() {
+        assert_eq!(GF(0x57).checked_div(GF(0x83)), Ok(GF(0x57) / GF(0x83)));
+        match GF(0x57).checked_div(GF(0)) {
+            Err(GFError::DivisionByZero) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,272 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! The Galois group of GF(2<sup>8</sup>) over GF(2), and the maps that are built from it.
+//!
+//! The Frobenius map <i>x</i> &rarr; <i>x</i><sup>2</sup> is an automorphism of the field,
+//! because (<i>a</i> + <i>b</i>)<sup>2</sup> = <i>a</i><sup>2</sup> + <i>b</i><sup>2</sup> in
+//! characteristic 2. Applying it eight times gives the identity, and its powers are all the
+//! automorphisms of the field, see [`Automorphism`].
+//!
+//! A linearized polynomial <i>c</i><sub>0</sub><i>x</i> +
+//! <i>c</i><sub>1</sub><i>x</i><sup>2</sup> + <i>c</i><sub>2</sub><i>x</i><sup>4</sup> +
+//! &middot;&middot;&middot; is a sum of multiples of Frobenius powers, so it is a GF(2)-linear
+//! map of the field to itself. Every such map can be written as a linearized polynomial with at
+//! most eight coefficients.
+
+use crate::GF;
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+/// Evaluates the linearized polynomial with the given coefficients at `x`, where `coeffs[i]` is
+/// the coefficient of <i>x</i><sup>2<sup>i</sup></sup>.
+///
+/// The result is GF(2)-linear in `x`: `eval_linearized(coeffs, a + b)` is equal to
+/// `eval_linearized(coeffs, a) + eval_linearized(coeffs, b)`. Every coefficient costs one
+/// squaring and one multiplication, and the running time does not depend on `x`.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, galois::eval_linearized};
+/// // x + x^2 + x^4 + ... + x^128 is the trace.
+/// let trace = [GF(1); 8];
+/// assert_eq!(eval_linearized(&trace, GF(0x20)), GF(GF(0x20).trace()));
+/// ```
+pub fn eval_linearized(coeffs: &[GF], x: GF) -> GF {
+    let mut result = GF(0);
+    let mut conjugate = x;
+
+    for &c in coeffs {
+        result += c * conjugate;
+        conjugate *= conjugate;
+    }
+
+    result
+}
+
+/// An automorphism <i>x</i> &rarr; <i>x</i><sup>2<sup>k</sup></sup> of GF(2<sup>8</sup>), that
+/// is the Frobenius map applied `k` times.
+///
+/// The automorphisms form the Galois group of GF(2<sup>8</sup>) over GF(2), which is cyclic of
+/// order 8, so `k` is always reduced modulo 8.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, galois::Automorphism};
+/// let square = Automorphism::frobenius();
+/// assert_eq!(square.apply(GF(0x53)), GF(0x53) * GF(0x53));
+///
+/// let sigma = Automorphism::new(3);
+/// assert_eq!(sigma.compose(sigma.inverse()), Automorphism::identity());
+/// ```
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub struct Automorphism {
+    k: u8,
+}
+
+impl Automorphism {
+    /// The automorphism <i>x</i> &rarr; <i>x</i><sup>2<sup>k</sup></sup>.
+    pub fn new(k: u32) -> Self {
+        Automorphism { k: (k % 8) as u8 }
+    }
+
+    /// The identity <i>x</i> &rarr; <i>x</i>.
+    pub fn identity() -> Self {
+        Automorphism::new(0)
+    }
+
+    /// The Frobenius map <i>x</i> &rarr; <i>x</i><sup>2</sup>, which generates the Galois
+    /// group.
+    pub fn frobenius() -> Self {
+        Automorphism::new(1)
+    }
+
+    /// The number of times `k` that the Frobenius map is applied, which is in `0..8`.
+    pub fn exponent(self) -> u32 {
+        self.k as u32
+    }
+
+    /// Applies the automorphism to `x` by squaring it `k` times. The running time depends on
+    /// `k`, but not on `x`.
+    pub fn apply(self, x: GF) -> GF {
+        let mut result = x;
+        for _ in 0..self.k {
+            result *= result;
+        }
+        result
+    }
+
+    /// The automorphism that first applies `other` and then `self`.
+    pub fn compose(self, other: Automorphism) -> Self {
+        Automorphism::new(u32::from(self.k + other.k))
+    }
+
+    /// The automorphism that undoes `self`, so that composing them gives the identity.
+    pub fn inverse(self) -> Self {
+        Automorphism::new(u32::from(8 - self.k))
+    }
+}
+
+/// The cyclotomic cosets of 2 modulo 255, which partition the exponents `0..255` into the sets
+/// {<i>s</i>, 2<i>s</i>, 4<i>s</i>, ...} modulo 255.
+///
+/// The Frobenius map sends &alpha;<sup>s</sup> to &alpha;<sup>2s</sup>, so the powers of a
+/// primitive element &alpha; with the exponents in one coset are conjugates, and they are the
+/// roots of the same minimal polynomial. Every coset starts with its smallest exponent, the
+/// cosets are sorted by it, and the size of a coset divides 8.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::galois::cyclotomic_cosets;
+/// let cosets = cyclotomic_cosets();
+///
+/// assert_eq!(cosets[0], [0]);
+/// assert_eq!(cosets[1], [1, 2, 4, 8, 16, 32, 64, 128]);
+/// assert!(cosets.contains(&vec![17, 34, 68, 136]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn cyclotomic_cosets() -> Vec<Vec<u8>> {
+    let mut seen = [false; 255];
+    let mut cosets = Vec::new();
+
+    for s in 0..255 {
+        if seen[s] {
+            continue;
+        }
+        let mut coset = vec![s as u8];
+        seen[s] = true;
+        let mut exponent = 2 * s % 255;
+        while exponent != s {
+            coset.push(exponent as u8);
+            seen[exponent] = true;
+            exponent = 2 * exponent % 255;
+        }
+        cosets.push(coset);
+    }
+
+    cosets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linearized_polynomial_is_linear() {
+        let coeffs = [GF(0x53), GF(0x00), GF(0x8d), GF(0x01), GF(0xf3)];
+
+        for a in 0u8..=255u8 {
+            for b in (0u8..=255u8).step_by(17) {
+                assert_eq!(
+                    eval_linearized(&coeffs, GF(a) + GF(b)),
+                    eval_linearized(&coeffs, GF(a)) + eval_linearized(&coeffs, GF(b))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn linearized_polynomial_matches_direct_evaluation() {
+        let coeffs = [GF(0x02), GF(0x1b), GF(0xca)];
+
+        for x in 0u8..=255u8 {
+            let x = GF(x);
+            let expected = GF(0x02) * x + GF(0x1b) * x.pow(2) + GF(0xca) * x.pow(4);
+            assert_eq!(eval_linearized(&coeffs, x), expected);
+        }
+        assert_eq!(eval_linearized(&[], GF(0x53)), GF(0));
+    }
+
+    #[test]
+    fn frobenius_has_order_eight() {
+        let frobenius = Automorphism::frobenius();
+        let mut sigma = Automorphism::identity();
+        for i in 0..8 {
+            assert_eq!(sigma.exponent(), i);
+            sigma = frobenius.compose(sigma);
+        }
+        assert_eq!(sigma, Automorphism::identity());
+
+        for x in 0u8..=255u8 {
+            let x = GF(x);
+            let applied = (0..8).fold(x, |y, _| frobenius.apply(y));
+            assert_eq!(applied, x);
+            assert_eq!(Automorphism::identity().apply(x), x);
+        }
+    }
+
+    #[test]
+    fn compose_is_associative() {
+        let (a, b, c) = (
+            Automorphism::new(3),
+            Automorphism::new(6),
+            Automorphism::new(13),
+        );
+        assert_eq!(a.compose(b).compose(c), a.compose(b.compose(c)));
+
+        for x in [GF(0x02), GF(0x53), GF(0xca), GF(0xff)] {
+            assert_eq!(a.compose(b).apply(x), a.apply(b.apply(x)));
+            assert_eq!(
+                a.compose(b).apply(c.apply(x)),
+                a.apply(b.compose(c).apply(x))
+            );
+            assert_eq!(c.inverse().apply(c.apply(x)), x);
+        }
+    }
+
+    #[test]
+    fn automorphisms_are_field_homomorphisms() {
+        let sigma = Automorphism::new(5);
+        for a in (0u8..=255u8).step_by(3) {
+            for b in (0u8..=255u8).step_by(11) {
+                let (a, b) = (GF(a), GF(b));
+                assert_eq!(sigma.apply(a + b), sigma.apply(a) + sigma.apply(b));
+                assert_eq!(sigma.apply(a * b), sigma.apply(a) * sigma.apply(b));
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn cyclotomic_cosets_partition_exponents() {
+        let cosets = cyclotomic_cosets();
+        // One coset per irreducible factor of x^255 - 1, which has one factor of degree 1, one
+        // of degree 2, three of degree 4 and 30 of degree 8.
+        assert_eq!(cosets.len(), 35);
+
+        let mut count = [0; 255];
+        for coset in &cosets {
+            assert_eq!(8 % coset.len(), 0);
+            for &e in coset {
+                count[e as usize] += 1;
+                assert!(coset.contains(&((2 * e as usize % 255) as u8)));
+            }
+
+            let root = GF::GENERATOR.pow(coset[0] as u32);
+            assert_eq!(
+                crate::GFPoly::minimal_polynomial(root).degree(),
+                Some(coset.len())
+            );
+        }
+        assert!(count.iter().all(|&c| c == 1));
+    }
+}
@@ -0,0 +1,329 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Polynomials over GF(2), such as the irreducible polynomials that define a finite field.
+//!
+//! A polynomial is stored as the bits of an `u64`, where bit `i` is the coefficient of
+//! <i>x</i><sup>i</sup>. The AES polynomial
+//! <i>x</i><sup>8</sup> + <i>x</i><sup>4</sup> + <i>x</i><sup>3</sup> + <i>x</i> + 1
+//! is therefore `0x11b`.
+//!
+//! These functions are meant for public polynomials, and are not isochronous.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// The degree of the polynomial, or `None` for the zero polynomial.
+pub fn degree(poly: u64) -> Option<u32> {
+    63u32.checked_sub(poly.leading_zeros())
+}
+
+/// Carry-less multiplication of two polynomials.
+fn clmul(a: u64, b: u64) -> u128 {
+    let mut product = 0;
+    for i in 0..64 {
+        if (b >> i) & 1 == 1 {
+            product ^= (a as u128) << i;
+        }
+    }
+    product
+}
+
+/// The remainder of `a` divided by the nonzero polynomial `modulus`.
+fn rem(mut a: u128, modulus: u64) -> u64 {
+    let modulus_degree = 63 - modulus.leading_zeros();
+    while a != 0 && 127 - a.leading_zeros() >= modulus_degree {
+        let shift = 127 - a.leading_zeros() - modulus_degree;
+        a ^= (modulus as u128) << shift;
+    }
+    a as u64
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let r = rem(a as u128, b);
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Calculates <i>x</i><sup>2<sup>k</sup></sup> modulo `modulus`.
+fn x_pow_pow2_mod(k: u32, modulus: u64) -> u64 {
+    let mut r = rem(0b10, modulus);
+    for _ in 0..k {
+        r = rem(clmul(r, r), modulus);
+    }
+    r
+}
+
+/// Calculates <i>x</i><sup>e</sup> modulo `modulus` with square and multiply.
+fn x_pow_mod(e: u64, modulus: u64) -> u64 {
+    let x = rem(0b10, modulus);
+    let mut result = rem(1, modulus);
+    for i in (0..64 - e.leading_zeros()).rev() {
+        result = rem(clmul(result, result), modulus);
+        if (e >> i) & 1 == 1 {
+            result = rem(clmul(result, x), modulus);
+        }
+    }
+    result
+}
+
+/// Tests whether a polynomial is irreducible over GF(2) with the Rabin test.
+///
+/// A polynomial <i>f</i> of degree <i>n</i> is irreducible if and only if it divides
+/// <i>x</i><sup>2<sup>n</sup></sup> - <i>x</i>, and
+/// gcd(<i>f</i>, <i>x</i><sup>2<sup>n/p</sup></sup> - <i>x</i>) = 1 for every prime <i>p</i>
+/// that divides <i>n</i>. Constant polynomials are not irreducible.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::gf2;
+/// // The AES polynomial x^8 + x^4 + x^3 + x + 1
+/// assert!(gf2::is_irreducible(0x11b));
+///
+/// // x^8 + 1 == (x + 1)^8
+/// assert!(!gf2::is_irreducible(0x101));
+/// ```
+pub fn is_irreducible(poly: u64) -> bool {
+    let n = match degree(poly) {
+        Some(n) if n > 0 => n,
+        _ => return false,
+    };
+
+    let x = rem(0b10, poly);
+    if x_pow_pow2_mod(n, poly) != x {
+        return false;
+    }
+
+    let mut remaining = n;
+    let mut p = 2;
+    while remaining > 1 {
+        if remaining % p == 0 {
+            if gcd(poly, x_pow_pow2_mod(n / p, poly) ^ x) != 1 {
+                return false;
+            }
+            while remaining % p == 0 {
+                remaining /= p;
+            }
+        }
+        p += 1;
+    }
+
+    true
+}
+
+/// Tests whether a polynomial is primitive, which means that it is irreducible and that
+/// <i>x</i> generates the whole multiplicative group of the field that it defines. Every nonzero
+/// element of that field is then a power of <i>x</i>, which is `GF(2)`.
+///
+/// The order of <i>x</i> is 2<sup>n</sup> - 1 if <i>x</i><sup>(2<sup>n</sup> - 1)/p</sup> is
+/// not 1 for every prime <i>p</i> that divides 2<sup>n</sup> - 1. The prime factors are found
+/// by trial division, so this only supports polynomials with a degree of at most 32.
+///
+/// # Panics
+/// Panics if the degree of the polynomial is larger than 32.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::gf2;
+/// // The AES polynomial is irreducible, but GF(2) only generates 51 elements.
+/// assert!(!gf2::is_primitive(0x11b));
+/// assert!(gf2::is_primitive(0x11d));
+/// ```
+pub fn is_primitive(poly: u64) -> bool {
+    assert!(
+        degree(poly).is_none_or(|n| n <= 32),
+        "the degree must be at most 32"
+    );
+    if !is_irreducible(poly) {
+        return false;
+    }
+
+    let order = (1u64 << degree(poly).unwrap_or(0)) - 1;
+    let mut remaining = order;
+    let mut p = 2;
+    while remaining > 1 {
+        if p * p > remaining {
+            // What remains is a prime.
+            p = remaining;
+        }
+        if remaining % p == 0 {
+            if x_pow_mod(order / p, poly) == 1 {
+                return false;
+            }
+            while remaining % p == 0 {
+                remaining /= p;
+            }
+        }
+        p += 1;
+    }
+
+    true
+}
+
+/// Finds the smallest primitive polynomial of the given degree, see [`is_primitive`]. Such a
+/// polynomial exists for every degree.
+///
+/// # Panics
+/// Panics if the degree is zero or larger than 32.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::gf2;
+/// // x^8 + x^4 + x^3 + x^2 + 1
+/// assert_eq!(gf2::find_primitive_polynomial(8), 0x11d);
+/// ```
+pub fn find_primitive_polynomial(degree: u32) -> u64 {
+    assert!(
+        (1..=32).contains(&degree),
+        "the degree must be between 1 and 32"
+    );
+
+    // Polynomials with a constant term of zero are divisible by x, so only odd ones are tried.
+    let first = 1u64 << degree;
+    (first + 1..first << 1)
+        .step_by(2)
+        .find(|&poly| is_primitive(poly))
+        .expect("a primitive polynomial exists for every degree")
+}
+
+/// All 30 irreducible polynomials of degree 8, in increasing order. Each of them defines
+/// GF(2<sup>8</sup>), for example as the polynomial of a [`GFn`](crate::GFn).
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::gf2;
+/// let polynomials = gf2::irreducible_polynomials_of_degree_8();
+///
+/// assert_eq!(polynomials.len(), 30);
+/// assert!(polynomials.contains(&0x11b));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn irreducible_polynomials_of_degree_8() -> Vec<u16> {
+    (0x101..0x200)
+        .step_by(2)
+        .filter(|&poly| is_irreducible(u64::from(poly)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn degree_of_polynomials() {
+        assert_eq!(degree(0), None);
+        assert_eq!(degree(1), Some(0));
+        assert_eq!(degree(0x11b), Some(8));
+        assert_eq!(degree(u64::MAX), Some(63));
+    }
+
+    #[test]
+    fn aes_polynomial_is_irreducible() {
+        assert!(is_irreducible(0x11b));
+        assert!(is_irreducible(0x11d));
+    }
+
+    #[test]
+    fn reducible_polynomials() {
+        // x^2
+        assert!(!is_irreducible(0b100));
+        // x^2 + 1 == (x + 1)^2
+        assert!(!is_irreducible(0b101));
+        // (x^2 + x + 1) * (x^3 + x + 1), which has no roots but is still reducible
+        assert!(!is_irreducible(0b11_0001));
+        // Constants
+        assert!(!is_irreducible(0));
+        assert!(!is_irreducible(1));
+    }
+
+    /// The multiplicative order of x modulo `poly`, by brute force.
+    fn order_of_x(poly: u64) -> u64 {
+        let mut power = rem(0b10, poly);
+        let mut order = 1;
+        while power != 1 {
+            power = rem(clmul(power, 0b10), poly);
+            order += 1;
+        }
+        order
+    }
+
+    #[test]
+    fn primitive_polynomials_generate_group() {
+        assert_eq!(find_primitive_polynomial(1), 0b11);
+        assert_eq!(find_primitive_polynomial(4), 0b1_0011);
+        assert_eq!(find_primitive_polynomial(8), 0x11d);
+
+        for degree in [2, 3, 4, 5, 8, 12] {
+            let poly = find_primitive_polynomial(degree);
+            assert!(is_irreducible(poly));
+            assert_eq!(order_of_x(poly), (1 << degree) - 1);
+        }
+    }
+
+    #[test]
+    fn irreducible_but_not_primitive() {
+        // x^4 + x^3 + x^2 + x + 1 divides x^5 - 1, so x has order 5.
+        assert!(is_irreducible(0b1_1111));
+        assert!(!is_primitive(0b1_1111));
+        assert_eq!(order_of_x(0x11b), 51);
+        assert!(!is_primitive(0x11b));
+        assert!(!is_primitive(0x101));
+    }
+
+    #[test]
+    fn large_primitive_polynomial() {
+        // x^32 + x^7 + x^5 + x^3 + x^2 + x + 1
+        assert!(is_primitive(0x1_0000_00af));
+        assert_eq!(degree(find_primitive_polynomial(32)), Some(32));
+    }
+
+    #[test]
+    fn small_irreducible_polynomials() {
+        assert!(is_irreducible(0b10));
+        assert!(is_irreducible(0b11));
+        assert!(is_irreducible(0b111));
+        assert!(is_irreducible(0b1011));
+        assert!(is_irreducible(0b1_0011));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn all_irreducible_polynomials_of_degree_8() {
+        let polynomials = irreducible_polynomials_of_degree_8();
+        assert_eq!(polynomials.len(), 30);
+        assert_eq!(polynomials[0], 0x11b);
+        assert!(polynomials.windows(2).all(|w| w[0] < w[1]));
+
+        for &poly in &polynomials {
+            assert_eq!(degree(u64::from(poly)), Some(8));
+            assert!(is_irreducible(u64::from(poly)));
+        }
+        let primitive = polynomials
+            .iter()
+            .filter(|&&poly| is_primitive(u64::from(poly)))
+            .count();
+        assert_eq!(primitive, 16);
+    }
+}
@@ -0,0 +1,214 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Isochronous arithmetic in GF(2<sup>32</sup>).
+
+use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// The irreducible pentanomial
+/// <i>x</i><sup>32</sup> + <i>x</i><sup>7</sup> + <i>x</i><sup>3</sup> + <i>x</i><sup>2</sup> + 1
+/// that is used for multiplication in GF(2<sup>32</sup>).
+pub const GF32_POLYNOMIAL: u64 = 0x1_0000_008d;
+
+/// Galois field wrapper struct for the finite field GF(2<sup>32</sup>).
+///
+/// It is wrapped around an `u32` type, to guarantee at compile time that
+/// all elements are in the finite field GF(2<sup>32</sup>). Multiplication is done modulo
+/// [`GF32_POLYNOMIAL`].
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::GF32;
+/// let element = GF32(0xdead_beef);
+/// let inverse = element.multiplicative_inverse();
+///
+/// assert_eq!(element * inverse, GF32(1));
+/// ```
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug, Hash)]
+pub struct GF32(pub u32);
+
+impl GF32 {
+    /// Creates an element from its representation. Every `u32` is a valid element, so this is
+    /// the same as the tuple constructor `GF32(x)`.
+    pub const fn new(x: u32) -> GF32 {
+        GF32(x)
+    }
+
+    /// Calculates the multiplicative inverse. The multiplicative inverse is the element in the
+    /// Galois field that results in a product of 1. Just like for [`GF`](crate::GF), the
+    /// inverse of `GF32(0)` is `GF32(0)`.
+    ///
+    /// The inverse is calculated with Fermat's little theorem, as
+    /// <i>a</i><sup>2<sup>32</sup> - 2</sup>. The exponent is a constant, so the same sequence
+    /// of multiplications is done for every element.
+    pub fn multiplicative_inverse(self) -> Self {
+        let mut result = GF32(1);
+
+        // All bits of 2^32 - 2 are set, except for the rightmost bit.
+        for _ in 0..31 {
+            result = result * result * self;
+        }
+
+        result * result
+    }
+}
+
+#[inline(always)]
+/// Extend the right most bit to all the other bits in the word.
+fn extend_bit(input: u32) -> u32 {
+    (((input) as i32) << 31).wrapping_shr(31) as u32
+}
+
+impl From<u32> for GF32 {
+    fn from(x: u32) -> Self {
+        GF32(x)
+    }
+}
+
+/// Adding two elements in the Galois field GF(2<sup>32</sup>) is equal to doing an exclusive
+/// or (XOR) between the two elements.
+/// It is also equal to subtracting two elements.
+impl Add for GF32 {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self::Output {
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl AddAssign for GF32 {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs)
+    }
+}
+
+/// Subtracting two elements in the Galois field GF(2<sup>32</sup>) is equal to doing an
+/// exclusive or (XOR) between the two elements.
+/// It is also equal to adding two elements.
+impl Sub for GF32 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl SubAssign for GF32 {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.add_assign(rhs)
+    }
+}
+
+/// Multiplication in this finite field is multiplication modulo [`GF32_POLYNOMIAL`].
+impl Mul for GF32 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut a = self.0;
+        let mut b = rhs.0;
+
+        let mut p = 0;
+
+        // This is the same shift and add algorithm as for GF(2^8), but once for every one of
+        // the 32 bits.
+        for _ in 0..32 {
+            p ^= extend_bit(b & 1) & a;
+            b >>= 1;
+
+            let carry = (a >> 31) & 1;
+            a <<= 1;
+
+            // Reduce with the irreducible polynomial with the high term eliminated.
+            a ^= extend_bit(carry) & (GF32_POLYNOMIAL as u32);
+        }
+
+        GF32(p)
+    }
+}
+
+impl MulAssign for GF32 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.mul(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A few hundred elements that are spread out over the whole field.
+    fn samples() -> impl Iterator<Item = GF32> {
+        (0u32..300).map(|i| GF32(i.wrapping_mul(0x9e37_79b9) ^ (i << 7)))
+    }
+
+    #[test]
+    fn polynomial_is_irreducible() {
+        assert!(crate::gf2::is_irreducible(GF32_POLYNOMIAL));
+    }
+
+    #[test]
+    fn multiplication_reduces_high_term() {
+        // x * x^31 == x^32 == x^7 + x^3 + x^2 + 1
+        assert_eq!(GF32(0b10) * GF32(0x8000_0000), GF32(0x8d));
+        assert_eq!(GF32(0x1234_5678) * GF32(1), GF32(0x1234_5678));
+        assert_eq!(GF32(0x1234_5678) * GF32(0), GF32(0));
+    }
+
+    #[test]
+    fn multiplication_is_associative() {
+        let b = GF32(0xcafe_babe);
+        let c = GF32(0x0bad_f00d);
+        for a in samples() {
+            assert_eq!((a * b) * c, a * (b * c));
+        }
+    }
+
+    #[test]
+    fn multiplication_distributes_over_addition() {
+        let b = GF32(0x8000_0001);
+        let c = GF32(0x7fff_ffff);
+        for a in samples() {
+            assert_eq!(a * (b + c), a * b + a * c);
+        }
+    }
+
+    #[test]
+    fn inverse_round_trip() {
+        for a in samples().filter(|&a| a != GF32(0)) {
+            assert_eq!(a * a.multiplicative_inverse(), GF32(1));
+        }
+        assert_eq!(GF32(1).multiplicative_inverse(), GF32(1));
+        assert_eq!(GF32(0).multiplicative_inverse(), GF32(0));
+    }
+
+    #[test]
+    fn add_and_subtract_assign() {
+        let mut x = GF32(0x1111_0000);
+        x += GF32(0x0101_0101);
+        assert_eq!(x, GF32(0x1010_0101));
+        x -= GF32(0x0101_0101);
+        assert_eq!(x, GF32(0x1111_0000));
+    }
+}
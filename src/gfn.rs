@@ -0,0 +1,221 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Isochronous arithmetic in GF(2<sup>8</sup>) with a reduction polynomial that is chosen at
+//! compile time.
+
+use crate::{extend_bit, nonzero_mask, GF};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// Galois field wrapper struct for GF(2<sup>8</sup>) with the reduction polynomial `POLY`.
+///
+/// `POLY` is a polynomial over GF(2) of degree 8, where bit `i` is the coefficient of
+/// <i>x</i><sup>i</sup>. It has to be irreducible for the elements to form a field, which is
+/// not checked. `GFn<0x11b>` behaves exactly like [`GF`], and they can be converted into each
+/// other.
+///
+/// To use another polynomial everywhere in a crate, define an alias for it. Type aliases can't
+/// be used as tuple struct constructors, so elements are created with `new` or `From` instead.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::GFn;
+/// // The polynomial x^8 + x^4 + x^3 + x^2 + 1, which is used by many Reed-Solomon codes.
+/// type GF = GFn<0x11d>;
+///
+/// let element = GF::new(2);
+/// assert_eq!(element * GF::from(128), GF::from(0x1d));
+/// assert_eq!(element * element.multiplicative_inverse(), GF::from(1));
+/// ```
+///
+/// A polynomial that doesn't have degree 8 fails to compile when elements are multiplied.
+/// ```compile_fail
+/// # use isochronous_finite_fields::GFn;
+/// let product = GFn::<0x1b>(2) * GFn(3);
+/// ```
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug, Hash)]
+#[repr(transparent)]
+pub struct GFn<const POLY: u16>(pub u8);
+
+impl<const POLY: u16> GFn<POLY> {
+    /// The reduction polynomial with the high term eliminated. Evaluating it fails the build if
+    /// `POLY` doesn't have degree 8.
+    const REDUCTION: u8 = {
+        assert!(
+            POLY >> 8 == 1,
+            "the reduction polynomial must have degree 8"
+        );
+        POLY as u8
+    };
+
+    /// Creates an element from its byte representation. Every byte is a valid element, so this
+    /// is the same as the tuple constructor `GFn(x)`, but it can also be used through a type
+    /// alias.
+    pub const fn new(x: u8) -> Self {
+        GFn(x)
+    }
+
+    /// Calculates the multiplicative inverse. The multiplicative inverse is the element in the
+    /// Galois field that results in a product of 1. Just like for [`GF`], the inverse of zero
+    /// is zero.
+    pub fn multiplicative_inverse(self) -> Self {
+        let mut p = 0;
+
+        for x in 0u8..=255u8 {
+            // Only the inverse results in a product of 1, for which y is zero.
+            let y = (self * GFn(x)).0 ^ 1;
+            p ^= !nonzero_mask(y) & x;
+        }
+
+        GFn(p)
+    }
+}
+
+impl<const POLY: u16> From<u8> for GFn<POLY> {
+    fn from(x: u8) -> Self {
+        GFn(x)
+    }
+}
+
+impl From<GF> for GFn<0x11b> {
+    fn from(x: GF) -> Self {
+        GFn(x.0)
+    }
+}
+
+impl From<GFn<0x11b>> for GF {
+    fn from(x: GFn<0x11b>) -> Self {
+        GF(x.0)
+    }
+}
+
+/// Adding two elements is equal to doing an exclusive or (XOR) between the two elements, no
+/// matter the reduction polynomial.
+impl<const POLY: u16> Add for GFn<POLY> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self::Output {
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl<const POLY: u16> AddAssign for GFn<POLY> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs)
+    }
+}
+
+/// Subtracting two elements is equal to adding them.
+impl<const POLY: u16> Sub for GFn<POLY> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl<const POLY: u16> SubAssign for GFn<POLY> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.add_assign(rhs)
+    }
+}
+
+/// Multiplication modulo the reduction polynomial `POLY`, with the same isochronous algorithm
+/// as the multiplication of [`GF`].
+impl<const POLY: u16> Mul for GFn<POLY> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut a = self.0;
+        let mut b = rhs.0;
+
+        let mut p = 0;
+
+        for _ in 0..8 {
+            p ^= extend_bit(b & 1) & a;
+            b >>= 1;
+
+            let carry = (a >> 7) & 1;
+            a <<= 1;
+            a ^= extend_bit(carry) & Self::REDUCTION;
+        }
+
+        GFn(p)
+    }
+}
+
+impl<const POLY: u16> MulAssign for GFn<POLY> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.mul(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aes_polynomial_matches_gf() {
+        for a in 0u8..=255u8 {
+            for b in [0x00, 0x01, 0x02, 0x13, 0x57, 0x83, 0xca, 0xff] {
+                let product: GF = (GFn::<0x11b>(a) * GFn(b)).into();
+                assert_eq!(product, GF(a) * GF(b));
+            }
+            assert_eq!(
+                GF::from(GFn::<0x11b>(a).multiplicative_inverse()),
+                GF(a).multiplicative_inverse()
+            );
+        }
+    }
+
+    #[test]
+    fn different_polynomials_give_different_products() {
+        // x^7 * x = x^8, which is reduced to the polynomial with the high term eliminated.
+        assert_eq!(GFn::<0x11b>(0x80) * GFn(0x02), GFn(0x1b));
+        assert_eq!(GFn::<0x11d>(0x80) * GFn(0x02), GFn(0x1d));
+        assert_ne!(
+            (GFn::<0x11b>(0x57) * GFn(0x83)).0,
+            (GFn::<0x11d>(0x57) * GFn(0x83)).0
+        );
+    }
+
+    #[test]
+    fn inverse_with_other_polynomial() {
+        for x in 1u8..=255u8 {
+            let x = GFn::<0x11d>(x);
+            assert_eq!(x * x.multiplicative_inverse(), GFn(1));
+        }
+        assert_eq!(GFn::<0x11d>(0).multiplicative_inverse(), GFn(0));
+    }
+
+    #[test]
+    fn addition_is_independent_of_polynomial() {
+        let mut x = GFn::<0x11d>(0x22);
+        x += GFn(0x81);
+        assert_eq!(x, GFn(0xa3));
+        x -= GFn(0x81);
+        assert_eq!(x, GFn(0x22));
+    }
+}
@@ -0,0 +1,282 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Erasure coding that is compatible with [ISA-L](https://github.com/intel/isa-l).
+//!
+//! ISA-L, and the erasure code backends built on top of it, use the reduction polynomial
+//! <i>x</i><sup>8</sup> + <i>x</i><sup>4</sup> + <i>x</i><sup>3</sup> + <i>x</i><sup>2</sup> + 1
+//! (`0x11d`) instead of the AES polynomial, so the arithmetic in this module is done with
+//! [`IsalGF`]. The generator matrix is the one of ISA-L's `gf_gen_rs_matrix`, and shards are
+//! encoded like `ec_encode_data` does. Shards that were encoded by ISA-L can therefore be
+//! reconstructed here, and the other way around.
+//!
+//! Just like in ISA-L, this generator matrix is not guaranteed to be MDS for every combination
+//! of parameters. When a combination of available shards can't be decoded,
+//! [`reconstruct_erasures`] returns [`GFError::SingularMatrix`].
+
+use crate::{GFError, GFn};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// An element of GF(2<sup>8</sup>) with the reduction polynomial that ISA-L uses.
+pub type IsalGF = GFn<0x11d>;
+
+/// Creates the generator matrix of ISA-L's `gf_gen_rs_matrix`, with `total_shards` rows and
+/// `data_shards` columns, in row-major order.
+///
+/// The first `data_shards` rows form the identity matrix. Parity row `r` consists of the powers
+/// of 2<sup>r</sup>, so entry `j` of that row is 2<sup>rj</sup>.
+///
+/// Returns [`GFError::OutOfRange`] if there are no data shards, if there are fewer shards in
+/// total than data shards, or if there are more than 255 shards in total.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::isal::generator_matrix;
+/// let matrix = generator_matrix(3, 5).unwrap();
+/// assert_eq!(matrix, [1, 0, 0, 0, 1, 0, 0, 0, 1, 1, 1, 1, 1, 2, 4]);
+/// ```
+pub fn generator_matrix(data_shards: usize, total_shards: usize) -> Result<Vec<u8>, GFError> {
+    if data_shards == 0 || total_shards < data_shards || total_shards > 255 {
+        return Err(GFError::OutOfRange);
+    }
+
+    let mut matrix = vec![0; data_shards * total_shards];
+    for i in 0..data_shards {
+        matrix[data_shards * i + i] = 1;
+    }
+
+    let mut generator = IsalGF::from(1);
+    for row in matrix.chunks_mut(data_shards).skip(data_shards) {
+        let mut p = IsalGF::from(1);
+        for entry in row {
+            *entry = p.0;
+            p *= generator;
+        }
+        generator *= IsalGF::from(2);
+    }
+
+    Ok(matrix)
+}
+
+/// Calculates the parity shards from the first `data_shards` shards, like ISA-L's
+/// `ec_encode_data` with the matrix of [`generator_matrix`]. The contents of the parity shards
+/// are overwritten.
+///
+/// Returns [`GFError::OutOfRange`] for invalid parameters, see [`generator_matrix`], and
+/// [`GFError::DimensionMismatch`] if the shards don't all have the same length.
+pub fn encode_shards(data_shards: usize, shards: &mut [Vec<u8>]) -> Result<(), GFError> {
+    let matrix = generator_matrix(data_shards, shards.len())?;
+    let length = shards[0].len();
+    if shards.iter().any(|shard| shard.len() != length) {
+        return Err(GFError::DimensionMismatch);
+    }
+
+    let (data, parity) = shards.split_at_mut(data_shards);
+    for (row, shard) in matrix.chunks(data_shards).skip(data_shards).zip(parity) {
+        shard.iter_mut().for_each(|b| *b = 0);
+        for (&c, input) in row.iter().zip(data.iter()) {
+            mul_add(IsalGF::from(c), input, shard);
+        }
+    }
+    Ok(())
+}
+
+/// Reconstructs the missing shards, which are marked as `None`. The shards are ordered as the
+/// data shards followed by the parity shards. Like ISA-L, the first `data_shards` available
+/// shards are used to recover the data.
+///
+/// Returns an error if
+/// * the parameters are invalid ([`GFError::OutOfRange`], see [`generator_matrix`]),
+/// * fewer than `data_shards` shards are available ([`GFError::TooFewShares`]),
+/// * the available shards have different lengths ([`GFError::DimensionMismatch`]),
+/// * the available shards can't be decoded with this matrix ([`GFError::SingularMatrix`]).
+pub fn reconstruct_erasures(
+    data_shards: usize,
+    shards: &mut [Option<Vec<u8>>],
+) -> Result<(), GFError> {
+    let matrix = generator_matrix(data_shards, shards.len())?;
+
+    let available: Vec<usize> = (0..shards.len()).filter(|&i| shards[i].is_some()).collect();
+    if available.len() < data_shards {
+        return Err(GFError::TooFewShares);
+    }
+    let length = shards[available[0]].as_ref().map_or(0, Vec::len);
+    if available
+        .iter()
+        .any(|&i| shards[i].as_ref().map_or(0, Vec::len) != length)
+    {
+        return Err(GFError::DimensionMismatch);
+    }
+
+    let chosen = &available[..data_shards];
+    let mut submatrix = Vec::with_capacity(data_shards * data_shards);
+    for &i in chosen {
+        submatrix.extend_from_slice(&matrix[data_shards * i..data_shards * (i + 1)]);
+    }
+    let decode = invert(data_shards, submatrix)?;
+
+    for j in 0..data_shards {
+        if shards[j].is_none() {
+            let mut shard = vec![0; length];
+            for (t, &i) in chosen.iter().enumerate() {
+                if let Some(input) = &shards[i] {
+                    mul_add(decode[data_shards * j + t], input, &mut shard);
+                }
+            }
+            shards[j] = Some(shard);
+        }
+    }
+
+    for (p, row) in matrix.chunks(data_shards).enumerate().skip(data_shards) {
+        if shards[p].is_none() {
+            let mut shard = vec![0; length];
+            for (&c, input) in row.iter().zip(shards.iter()) {
+                if let Some(input) = input {
+                    mul_add(IsalGF::from(c), input, &mut shard);
+                }
+            }
+            shards[p] = Some(shard);
+        }
+    }
+    Ok(())
+}
+
+/// Inverts the `n` by `n` row-major matrix with Gauss-Jordan elimination.
+fn invert(n: usize, matrix: Vec<u8>) -> Result<Vec<IsalGF>, GFError> {
+    let mut m: Vec<IsalGF> = matrix.into_iter().map(IsalGF::from).collect();
+    let mut inverse = vec![IsalGF::from(0); n * n];
+    for i in 0..n {
+        inverse[n * i + i] = IsalGF::from(1);
+    }
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .find(|&r| m[n * r + col].0 != 0)
+            .ok_or(GFError::SingularMatrix)?;
+        for c in 0..n {
+            m.swap(n * col + c, n * pivot + c);
+            inverse.swap(n * col + c, n * pivot + c);
+        }
+
+        let scale = m[n * col + col].multiplicative_inverse();
+        for c in 0..n {
+            m[n * col + c] *= scale;
+            inverse[n * col + c] *= scale;
+        }
+
+        for r in (0..n).filter(|&r| r != col) {
+            let factor = m[n * r + col];
+            for c in 0..n {
+                let (a, b) = (m[n * col + c], inverse[n * col + c]);
+                m[n * r + c] -= factor * a;
+                inverse[n * r + c] -= factor * b;
+            }
+        }
+    }
+
+    Ok(inverse)
+}
+
+/// Calculates `output[i] += coefficient * input[i]` for every byte.
+fn mul_add(coefficient: IsalGF, input: &[u8], output: &mut [u8]) {
+    for (o, &i) in output.iter_mut().zip(input) {
+        *o ^= (coefficient * IsalGF::from(i)).0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Four data shards and three parity shards, as encoded by ISA-L's `ec_encode_data` with the
+    /// matrix of `gf_gen_rs_matrix(a, 7, 4)`.
+    fn reference_shards() -> Vec<Vec<u8>> {
+        vec![
+            b"ISA-L ec".to_vec(),
+            b"Jerasure".to_vec(),
+            b"0x11d RS".to_vec(),
+            b"vectors!".to_vec(),
+            vec![0x45, 0x2b, 0x61, 0x09, 0x34, 0x07, 0x36, 0x74],
+            vec![0x8a, 0x6b, 0x5e, 0xac, 0x78, 0xfd, 0x6b, 0xed],
+            vec![0x97, 0x71, 0x46, 0xcf, 0x51, 0x02, 0x68, 0x1b],
+        ]
+    }
+
+    #[test]
+    fn reference_matrix() {
+        let matrix = generator_matrix(4, 7).unwrap();
+        assert_eq!(&matrix[16..], &[1, 1, 1, 1, 1, 2, 4, 8, 1, 4, 16, 64]);
+
+        // The powers of 2 wrap around with the 0x11d polynomial.
+        let matrix = generator_matrix(5, 8).unwrap();
+        assert_eq!(&matrix[35..], &[1, 4, 16, 64, 0x1d]);
+    }
+
+    #[test]
+    fn encode_matches_reference() {
+        let reference = reference_shards();
+        let mut shards = reference.clone();
+        for shard in &mut shards[4..] {
+            shard.iter_mut().for_each(|b| *b = 0xff);
+        }
+
+        encode_shards(4, &mut shards).unwrap();
+        assert_eq!(shards, reference);
+    }
+
+    #[test]
+    fn reconstruct_reference_shards() {
+        let reference = reference_shards();
+        for a in 0..7 {
+            for b in a + 1..7 {
+                for c in b + 1..7 {
+                    let mut received: Vec<_> = reference.iter().cloned().map(Some).collect();
+                    received[a] = None;
+                    received[b] = None;
+                    received[c] = None;
+
+                    reconstruct_erasures(4, &mut received).unwrap();
+                    let received: Vec<_> = received.into_iter().map(Option::unwrap).collect();
+                    assert_eq!(received, reference);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_parameters() {
+        assert_eq!(generator_matrix(0, 3), Err(GFError::OutOfRange));
+        assert_eq!(generator_matrix(4, 3), Err(GFError::OutOfRange));
+        assert_eq!(generator_matrix(4, 256), Err(GFError::OutOfRange));
+
+        let mut received: Vec<_> = reference_shards().into_iter().map(Some).collect();
+        received[0] = None;
+        received[1] = None;
+        received[2] = None;
+        received[3] = None;
+        assert_eq!(
+            reconstruct_erasures(4, &mut received),
+            Err(GFError::TooFewShares)
+        );
+    }
+}
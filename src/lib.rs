@@ -55,17 +55,196 @@
 //! assert_eq!(GF(110).multiplicative_inverse(), GF(33));
 //! assert_eq!(GF(110) * GF(33), GF(1));
 //! ```
+//!
+//! # Features
+//! * `alloc` (enabled by default): polynomials over GF(2<sup>8</sup>) with `GFPoly`, matrices
+//!   with `GFMatrix`, Reed-Solomon codes in `reed_solomon`, BCH codes in `bch`, ISA-L
+//!   compatible erasure coding in `isal`, masked arithmetic in `masking` and fields that are
+//!   chosen at runtime in `dyn_field`. This requires an allocator.
+//! * `compat`: the `compat` module, with free functions such as `gf_mul` on raw bytes, for
+//!   porting code from other crates.
+//! * `counting`: the `counting` module, which counts field operations for the analysis of
+//!   algorithms. This requires the standard library.
+//! * `rayon`: the `parallel` module, with multi-threaded bulk operations and Reed-Solomon
+//!   encoding. This requires the standard library and enables `alloc`.
+//! * `tables`: the `tables` module, which calculates the full addition and multiplication
+//!   tables for tools and documentation. They are 64 KiB each, so they are not meant for
+//!   arithmetic. It also contains the AES S-box tables, which are calculated at compile time.
+//! * `bytemuck`: implementations of `bytemuck::Pod` and `bytemuck::Zeroable` for [`GF`], so
+//!   that slices of elements can be cast with the `bytemuck` crate.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(feature = "counting", feature = "rayon"))]
+extern crate std;
+
+mod accumulator;
+pub mod aes;
+pub mod basis;
+pub mod bit_serial;
+#[cfg(feature = "compat")]
+pub mod compat;
+#[cfg(feature = "counting")]
+pub mod counting;
+mod error;
+pub mod galois;
+pub mod gf2;
+mod gf32;
+mod gfn;
+mod rolling;
+pub mod shamir;
+pub mod slice;
+#[cfg(feature = "tables")]
+pub mod tables;
+mod vector;
+
+pub use accumulator::ClmulAccumulator;
+pub use error::GFError;
+pub use gf32::{GF32, GF32_POLYNOMIAL};
+pub use gfn::GFn;
+pub use rolling::RollingHash;
+pub use vector::GFVec;
 
-use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+#[cfg(feature = "alloc")]
+pub mod bch;
+#[cfg(feature = "alloc")]
+pub mod dyn_field;
+#[cfg(feature = "alloc")]
+pub mod isal;
+#[cfg(feature = "alloc")]
+pub mod masking;
+#[cfg(feature = "alloc")]
+mod matrix;
+#[cfg(feature = "alloc")]
+pub mod ntt;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "alloc")]
+mod poly;
+#[cfg(feature = "alloc")]
+pub mod reed_solomon;
+#[cfg(feature = "alloc")]
+mod solver;
+
+#[cfg(feature = "alloc")]
+pub use matrix::GFMatrix;
+#[cfg(feature = "alloc")]
+pub use poly::{GFPoly, HornerEval};
+#[cfg(feature = "alloc")]
+pub use reed_solomon::{Interleaver, ReedSolomon, StreamingRsEncoder, SyndromeAccumulator};
+#[cfg(feature = "alloc")]
+pub use solver::OnlineSolver;
+
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
 
 /// Galois field wrapper struct.
 ///
 /// It is wrapped around an `u8` type, to guarantee at compile time that
 /// all elements are in the finite field GF(2<sup>8</sup>).
+///
+/// `GF` is guaranteed to have the same memory layout as `u8`, so slices of bytes can be
+/// reinterpreted as slices of field elements without copying. See [`slice::from_bytes`] and
+/// [`slice::as_bytes`].
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug, Hash)]
+#[repr(transparent)]
 pub struct GF(pub u8);
 
+// SAFETY: GF is a #[repr(transparent)] wrapper around u8, so every bit pattern is valid, the
+// zero byte is GF(0) and there is no padding.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for GF {}
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for GF {}
+
 impl GF {
+    /// A generator of the multiplicative group of GF(2<sup>8</sup>). Every nonzero element is a
+    /// power of `GF(3)`. Note that `GF(2)` is not a generator for the AES polynomial, because
+    /// its powers only cover 51 elements.
+    pub const GENERATOR: GF = GF(3);
+
+    /// Creates an element from its byte representation. Every byte is a valid element, so this
+    /// is the same as the tuple constructor `GF(x)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF::new(83), GF(83));
+    /// ```
+    pub const fn new(x: u8) -> GF {
+        GF(x)
+    }
+
+    /// Creates an element from a value that has to fit in a byte, like [`GF::new`] does for
+    /// bytes. This is meant for constants
+    /// in lookup tables and matrices: in a const context, a value larger than 255 fails the
+    /// build instead of being truncated silently. At runtime, it panics instead.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// const ELEMENT: GF = GF::checked_from(0x53);
+    /// assert_eq!(ELEMENT, GF(0x53));
+    /// ```
+    ///
+    /// A value that doesn't fit in a byte fails to compile.
+    /// ```compile_fail
+    /// # use isochronous_finite_fields::GF;
+    /// const ELEMENT: GF = GF::checked_from(0x11b);
+    /// let element = ELEMENT;
+    /// ```
+    pub const fn checked_from(x: u16) -> GF {
+        assert!(x <= 0xff, "a field element must fit in a byte");
+        GF(x as u8)
+    }
+
+    /// Multiplies two elements, like the `*` operator, but as a `const fn`, so that products can
+    /// be calculated at compile time. It is the same isochronous multiplication.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// const PRODUCT: GF = GF(0x53).mul_const(GF(0xca));
+    /// assert_eq!(PRODUCT, GF(0x53) * GF(0xca));
+    /// ```
+    pub const fn mul_const(self, rhs: GF) -> GF {
+        let mut a = self.0;
+        let mut b = rhs.0;
+
+        let mut p = 0;
+
+        // Implementation details from https://en.wikipedia.org/wiki/Finite_field_arithmetic
+        // Run the following loop eight times (once per bit).
+        let mut i = 0;
+        while i < 8 {
+            // If the rightmost bit of b is set, exclusive OR the product p by the value of a.
+            // This is polynomial addition.
+            p ^= extend_bit(b & 1) & a;
+
+            // Shift b one bit to the right, discarding the rightmost bit, and making the leftmost
+            // bit have a value of zero. This divides the polynomial by x, discarding the x0 term.
+            b >>= 1;
+
+            // Keep track of whether the leftmost bit of a is set to one and call this value carry.
+            let carry = (a >> 7) & 1;
+
+            // Shift a one bit to the left, discarding the leftmost bit, and making the new
+            // rightmost bit zero. This multiplies the polynomial by x, but we still need to take
+            // account of carry which represented the coefficient of x7.
+            a <<= 1;
+
+            // If carry had a value of one, exclusive or a with the hexadecimal
+            // number 0x1b (00011011 in binary). 0x1b corresponds to the irreducible polynomial with
+            // the high term eliminated. Conceptually, the high term of the irreducible polynomial
+            // and carry add modulo 2 to 0.
+            a ^= extend_bit(carry & 1) & 0x1b;
+
+            i += 1;
+        }
+
+        // p now has the product
+        GF(p)
+    }
+
     /// Calculates the multiplicative inverse. The multiplicative inverse is the element in the
     /// Galois field that results in a product of 1.
     ///
@@ -78,6 +257,32 @@ impl GF {
     /// assert_eq!(element * inverse, GF(1));
     /// ```
     pub fn multiplicative_inverse(self) -> Self {
+        self.inverse_with(InverseMethod::ItohTsujii)
+    }
+
+    /// Calculates the multiplicative inverse with the given method. All methods give the same
+    /// result, including `GF(0)` for the inverse of zero, and they are all isochronous. They only
+    /// differ in speed, which depends on the platform.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, InverseMethod};
+    /// let element = GF(148);
+    ///
+    /// assert_eq!(
+    ///     element.inverse_with(InverseMethod::Fermat),
+    ///     element.inverse_with(InverseMethod::BruteForce)
+    /// );
+    /// ```
+    pub fn inverse_with(self, method: InverseMethod) -> Self {
+        match method {
+            InverseMethod::BruteForce => self.inverse_brute_force(),
+            InverseMethod::Fermat => self.pow(254),
+            InverseMethod::ItohTsujii => self.inverse_itoh_tsujii(),
+        }
+    }
+
+    fn inverse_brute_force(self) -> Self {
         let mut p = 0;
 
         for x in 0u8..=255u8 {
@@ -85,25 +290,504 @@ impl GF {
             // If non-zero, the multiplication ends with something different.
             let y = (self * GF(x)).0 ^ 1;
 
-            // OR all bits together in the rightmost bit. If y is zero, that means that the
-            // result of ORing all bits together will also be zero. Otherwise, it will be 1.
-            let or = y | y >> 1 | y >> 2 | y >> 3 | y >> 4 | y >> 5 | y >> 6 | y >> 7;
-
-            // Extend the bits to the full byte and negate it. This means that the AND will
-            // be zero if the multiplication in y was 1.
-            p ^= !extend_bit(or) & x;
+            // Negate the nonzero mask of y. This means that the AND will be zero if the
+            // multiplication in y was 1.
+            p ^= !nonzero_mask(y) & x;
         }
 
         GF(p)
     }
+
+    fn inverse_itoh_tsujii(self) -> Self {
+        // a_k is self^(2^k - 1), and a_(j + k) == a_j^(2^k) * a_k.
+        let a1 = self;
+        let a2 = a1 * a1 * a1;
+        let a3 = a2 * a2 * a1;
+        let mut a3_pow_8 = a3;
+        for _ in 0..3 {
+            a3_pow_8 *= a3_pow_8;
+        }
+        let a6 = a3_pow_8 * a3;
+        let a7 = a6 * a6 * a1;
+
+        // The inverse is self^254 == (self^127)^2 == a_7^2.
+        a7 * a7
+    }
+
+    /// Calculates the multiplicative inverses of all elements, where element `x` of the table is
+    /// the inverse of `GF(x)`. Just like for [`GF::multiplicative_inverse`], the inverse of zero
+    /// is zero.
+    ///
+    /// This is meant to be calculated once, and then stored by the caller. Every inverse is
+    /// calculated isochronously, but looking up a secret index in the table is not, see
+    /// [`slice::ct_select`] for that.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// let inverses = GF::inverse_table();
+    /// assert_eq!(inverses[0x53], GF(0xca));
+    /// ```
+    pub fn inverse_table() -> [GF; 256] {
+        let mut table = [GF(0); 256];
+
+        for (x, inverse) in table.iter_mut().enumerate() {
+            *inverse = GF(x as u8).multiplicative_inverse();
+        }
+
+        table
+    }
+
+    /// Calculates the [`discrete_log`](GF::discrete_log) of all elements at once, where element
+    /// `x` of the table is the logarithm of `GF(x)` with respect to [`GF::GENERATOR`]. Zero has
+    /// no logarithm, so element 0 is left as 0, which is also the logarithm of `GF(1)`.
+    ///
+    /// The powers of the generator are visited once, with 254 multiplications in total, which
+    /// is much faster than 255 separate discrete logarithms. The table is filled in the same
+    /// order for every call, but just like [`GF::inverse_table`], looking up a secret index is
+    /// not isochronous.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// let logs = GF::compute_log_table();
+    /// assert_eq!(logs[5], 2);
+    /// assert_eq!(GF::GENERATOR.pow(u32::from(logs[0x53])), GF(0x53));
+    /// ```
+    pub fn compute_log_table() -> [u8; 256] {
+        let mut table = [0; 256];
+        let mut power = GF(1);
+
+        for log in 1..255 {
+            power *= GF::GENERATOR;
+            table[power.0 as usize] = log;
+        }
+
+        table
+    }
+
+    /// Raises the element to the power `exp` with square and multiply.
+    ///
+    /// The sequence of operations depends on the bits of the exponent, so the exponent should
+    /// not be secret. The running time does not depend on the element itself.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF(3).pow(2), GF(3) * GF(3));
+    ///
+    /// // The multiplicative group of GF(2^8) has order 255.
+    /// assert_eq!(GF(148).pow(255), GF(1));
+    /// ```
+    pub fn pow(self, exp: u32) -> Self {
+        let mut result = GF(1);
+        let mut base = self;
+        let mut exp = exp;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+
+        result
+    }
+
+    /// Raises the element to the power `exp`, which can be negative. A negative exponent raises
+    /// the [`multiplicative_inverse`](GF::multiplicative_inverse) to the power `-exp`, and any
+    /// element to the power 0 is `GF(1)`.
+    ///
+    /// Since the inverse of `GF(0)` is defined to be `GF(0)`, `GF(0)` to a negative power is
+    /// `GF(0)`. Like [`GF::pow`], the exponent should not be secret.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF(3).pow_signed(-2) * GF(3).pow(2), GF(1));
+    /// assert_eq!(GF(0).pow_signed(-1), GF(0));
+    /// ```
+    pub fn pow_signed(self, exp: i32) -> Self {
+        if exp < 0 {
+            self.multiplicative_inverse().pow(exp.unsigned_abs())
+        } else {
+            self.pow(exp as u32)
+        }
+    }
+
+    /// The multiplicative order of the element, which is the smallest positive `d` for which
+    /// the element to the power `d` is `GF(1)`. It is a divisor of 255. `GF(0)` has no
+    /// multiplicative order, so it returns 0.
+    ///
+    /// The order is found by trying the divisors of 255 in increasing order, so the running
+    /// time depends on the result.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF::GENERATOR.multiplicative_order(), 255);
+    /// assert_eq!(GF(2).multiplicative_order(), 51);
+    /// assert_eq!(GF(1).multiplicative_order(), 1);
+    /// ```
+    pub fn multiplicative_order(self) -> u16 {
+        if self == GF(0) {
+            return 0;
+        }
+        [1, 3, 5, 15, 17, 51, 85, 255]
+            .iter()
+            .copied()
+            .find(|&d| self.pow(u32::from(d)) == GF(1))
+            .unwrap_or(255)
+    }
+
+    /// A generator of the multiplicative subgroup of order `order`, which is an element with
+    /// exactly that [`multiplicative_order`](GF::multiplicative_order). This is the root of
+    /// unity of the number theoretic transforms in `ntt`.
+    ///
+    /// The multiplicative group is cyclic with 255 elements, so there is a subgroup for every
+    /// divisor of 255, and it is generated by [`GF::GENERATOR`] to the power 255 / `order`.
+    /// Returns `None` if `order` doesn't divide 255.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// let root = GF::subgroup_generator(17).unwrap();
+    ///
+    /// assert_eq!(root.multiplicative_order(), 17);
+    /// assert_eq!(GF::subgroup_generator(7), None);
+    /// ```
+    pub fn subgroup_generator(order: u16) -> Option<GF> {
+        if order == 0 || 255 % order != 0 {
+            return None;
+        }
+        Some(GF::GENERATOR.pow(u32::from(255 / order)))
+    }
+
+    /// Raises the element to the power `exp` with a Montgomery ladder.
+    ///
+    /// Unlike [`GF::pow`], the sequence of operations is the same for every exponent: every bit
+    /// of the exponent costs exactly one multiplication and one squaring, and the bits are only
+    /// used as masks for branch free swaps. Both the element and the exponent can therefore be
+    /// secret. This does make it slower than [`GF::pow`] for small exponents.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF(148).pow_ct(254), GF(148).multiplicative_inverse());
+    /// ```
+    pub fn pow_ct(self, exp: u32) -> Self {
+        let mut r0 = GF(1);
+        let mut r1 = self;
+
+        // Keep the invariant r1 == r0 * self, while going from the leftmost to the rightmost
+        // bit of the exponent.
+        for i in (0..32).rev() {
+            let mask = extend_bit((exp >> i) as u8 & 1);
+
+            // If the bit is set, swap r0 and r1 before and after the ladder step, so that
+            // r0 = r0 * r1 and r1 = r1 * r1. Otherwise, r1 = r0 * r1 and r0 = r0 * r0.
+            conditional_swap(&mut r0, &mut r1, mask);
+            r1 = r0 * r1;
+            r0 = r0 * r0;
+            conditional_swap(&mut r0, &mut r1, mask);
+        }
+
+        r0
+    }
+
+    /// Calculates [`GF::GENERATOR`] to the power `n` with square and multiply, without a table.
+    ///
+    /// Every bit of `n` costs a squaring and a multiplication, of which the result is only kept
+    /// with a mask if the bit is set. The running time therefore does not depend on `n`.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF::exp_generator(2), GF(5));
+    /// assert_eq!(GF::exp_generator(255), GF(1));
+    /// ```
+    pub fn exp_generator(n: u8) -> GF {
+        let mut result = GF(1);
+
+        for i in (0..8).rev() {
+            let mask = extend_bit((n >> i) & 1);
+            result *= result;
+            let multiplied = result * GF::GENERATOR;
+            result = GF((mask & multiplied.0) | (!mask & result.0));
+        }
+
+        result
+    }
+
+    /// Calculates <i>x</i> (`GF(2)`) to the power `n`, by multiplying by <i>x</i> one step at a
+    /// time. Every step is a single shift and a masked reduction, which is much cheaper than a
+    /// general multiplication.
+    ///
+    /// The element <i>x</i> has order 51 for the AES polynomial, so `n` is first reduced modulo
+    /// 51 and at most 50 steps are needed. This also means that <i>x</i> does not generate
+    /// the whole multiplicative group, see [`GF::exp_generator`] for that. The number of steps
+    /// depends on `n`, so `n` should not be secret.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF::pow2(8), GF(0x1b));
+    /// assert_eq!(GF::pow2(51), GF(1));
+    /// ```
+    pub fn pow2(n: u32) -> GF {
+        let mut result = 1u8;
+
+        for _ in 0..n % 51 {
+            let carry = extend_bit(result >> 7);
+            result = (result << 1) ^ (carry & 0x1b);
+        }
+
+        GF(result)
+    }
+
+    /// Calculates the discrete logarithm of the element with respect to [`GF::GENERATOR`],
+    /// which is the `n` in `0..255` for which [`GF::exp_generator`]`(n)` is equal to the
+    /// element. Zero has no logarithm.
+    ///
+    /// This is a baby-step giant-step algorithm without a table: all 16 giant steps are compared
+    /// to all 16 baby steps with masks, so the whole group is always scanned. The running time
+    /// only depends on whether the element is zero.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF(5).discrete_log(), Some(2));
+    /// assert_eq!(GF(0).discrete_log(), None);
+    /// ```
+    pub fn discrete_log(self) -> Option<u8> {
+        let mut baby_steps = [GF(1); 16];
+        for j in 1..16 {
+            baby_steps[j] = baby_steps[j - 1] * GF::GENERATOR;
+        }
+        // The inverse of GENERATOR^16, since the group has order 255.
+        let giant_step = GF::exp_generator(255 - 16);
+
+        let mut log = 0;
+        let mut found = 0;
+        let mut giant = self;
+        for i in 0..16u8 {
+            for (j, &baby) in baby_steps.iter().enumerate() {
+                // Only the first match counts, since both 0 and 255 match the element 1.
+                let equal = !nonzero_mask(giant.0 ^ baby.0) & !found;
+                log |= equal & (16 * i + j as u8);
+                found |= equal;
+            }
+            giant *= giant_step;
+        }
+
+        if found != 0 {
+            Some(log)
+        } else {
+            None
+        }
+    }
+
+    /// Divides by `rhs`, like the `/` operator, but returns [`GFError::DivisionByZero`]
+    /// instead of `GF(0)` when `rhs` is `GF(0)`.
+    ///
+    /// Checking for zero is not isochronous, so use the `/` operator when `rhs` is secret.
+    pub fn checked_div(self, rhs: Self) -> Result<Self, GFError> {
+        if rhs == GF(0) {
+            Err(GFError::DivisionByZero)
+        } else {
+            Ok(self / rhs)
+        }
+    }
+
+    /// The Hamming weight of the element, which is the number of bits that are set in its
+    /// byte representation.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF(0b1011_0001).hamming_weight(), 4);
+    /// ```
+    pub fn hamming_weight(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// The parity of the element, which is the exclusive or (XOR) of all bits in its byte
+    /// representation. It is 1 if the Hamming weight is odd, and 0 otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF(0b1011_0001).parity(), 0);
+    /// assert_eq!(GF(0b1011_0101).parity(), 1);
+    /// ```
+    pub fn parity(self) -> u8 {
+        let mut x = self.0;
+        x ^= x >> 4;
+        x ^= x >> 2;
+        x ^= x >> 1;
+        x & 1
+    }
+
+    /// Precomputes the multiplication by this element, to multiply eight packed lanes at a time
+    /// with [`slice::ScaleTable::apply_u64`]. This pays off when a whole buffer is scaled by
+    /// the same coefficient.
+    pub fn precompute_scale(self) -> slice::ScaleTable {
+        slice::ScaleTable::new(self)
+    }
+
+    /// Returns `0xff` if the element is nonzero, and `0x00` if it is `GF(0)`, without branches.
+    /// The mask selects between values with `&` and `|`, like the pivot of an isochronous
+    /// elimination: `(mask & a) | (!mask & b)` is `a` for a nonzero element and `b` otherwise.
+    ///
+    /// All bits are ORed into the lowest bit, which is then extended to the whole byte, the same
+    /// way as the brute force [`InverseMethod`] selects the inverse.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF(0x10).nonzero_mask(), 0xff);
+    /// assert_eq!(GF(0).nonzero_mask(), 0x00);
+    /// ```
+    pub fn nonzero_mask(self) -> u8 {
+        nonzero_mask(self.0)
+    }
+
+    /// Returns `0xff` if the integer representation of the element is larger than that of
+    /// `other`, and `0x00` otherwise, without branches. This is meant for sorting networks and
+    /// other constant-time algorithms on keys that are stored as field elements.
+    ///
+    /// This compares the bytes as integers: a finite field has no ordering that is compatible
+    /// with its arithmetic, so the result says nothing about the elements as field elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF(0x80).ct_gt(GF(0x7f)), 0xff);
+    /// assert_eq!(GF(0x7f).ct_gt(GF(0x7f)), 0x00);
+    /// ```
+    pub fn ct_gt(self, other: GF) -> u8 {
+        // other - self borrows exactly when self > other, which sets the high byte.
+        let difference = (other.0 as u16).wrapping_sub(self.0 as u16);
+        extend_bit((difference >> 8) as u8)
+    }
+
+    /// The absolute trace of the element, which is the sum of its conjugates
+    /// <i>a</i> + <i>a</i><sup>2</sup> + <i>a</i><sup>4</sup> + &middot;&middot;&middot; +
+    /// <i>a</i><sup>128</sup>. The trace is always 0 or 1, and it is linear: the trace of a sum
+    /// is the sum of the traces.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF(1).trace(), 0);
+    /// assert_eq!(GF(0x20).trace(), 1);
+    /// ```
+    pub fn trace(self) -> u8 {
+        let mut conjugate = self;
+        let mut sum = self;
+        for _ in 1..8 {
+            conjugate *= conjugate;
+            sum += conjugate;
+        }
+        sum.0
+    }
+
+    /// The conjugates of the element, which is its orbit <i>a</i>, <i>a</i><sup>2</sup>,
+    /// <i>a</i><sup>4</sup>, ..., <i>a</i><sup>128</sup> under the Frobenius map, without
+    /// duplicates. The number of conjugates divides 8, and they are the roots of
+    /// [`GFPoly::minimal_polynomial`].
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert_eq!(GF(1).conjugates(), [GF(1)]);
+    /// assert_eq!(GF::GENERATOR.conjugates().len(), 8);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn conjugates(self) -> alloc::vec::Vec<GF> {
+        let mut conjugates = alloc::vec![self];
+        let mut conjugate = self * self;
+        while conjugate != self {
+            conjugates.push(conjugate);
+            conjugate *= conjugate;
+        }
+        conjugates
+    }
+
+    /// Whether `other` is one of the `conjugates` of the element, so that both have the same
+    /// `GFPoly::minimal_polynomial`. Being conjugate is an equivalence relation, and its classes
+    /// among the nonzero elements correspond to the `galois::cyclotomic_cosets` of their
+    /// exponents.
+    ///
+    /// All eight powers <i>a</i><sup>2<sup>i</sup></sup> are compared, so the running time does
+    /// not depend on the elements.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// assert!(GF(2).is_conjugate_to(GF(4)));
+    /// assert!(!GF(2).is_conjugate_to(GF(3)));
+    /// ```
+    pub fn is_conjugate_to(self, other: GF) -> bool {
+        let mut conjugate = self;
+        let mut found = 0;
+        for _ in 0..8 {
+            found |= !nonzero_mask(conjugate.0 ^ other.0);
+            conjugate *= conjugate;
+        }
+        found != 0
+    }
+
+    /// The basis that is dual to the polynomial basis 1, <i>x</i>, ..., <i>x</i><sup>7</sup>
+    /// under the trace form, so that Tr(<i>x</i><sup>i</sup> `dual[j]`) is 1 if `i == j` and 0
+    /// otherwise. This is the same basis as [`basis::DUAL_BASIS`], which has more details about
+    /// its use in bit-serial multipliers.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GF;
+    /// let dual = GF::trace_dual_basis();
+    /// assert_eq!((GF(0x08) * dual[3]).trace(), 1);
+    /// assert_eq!((GF(0x08) * dual[4]).trace(), 0);
+    /// ```
+    pub fn trace_dual_basis() -> [GF; 8] {
+        basis::DUAL_BASIS
+    }
 }
 
 #[inline(always)]
 /// Extend the right most bit to all the other bits in the byte.
-fn extend_bit(input: u8) -> u8 {
+const fn extend_bit(input: u8) -> u8 {
     (((input) as i8) << 7).wrapping_shr(7) as u8
 }
 
+#[inline(always)]
+/// Swap `a` and `b` if the mask is `0xff`, and leave them alone if the mask is `0x00`.
+fn conditional_swap(a: &mut GF, b: &mut GF, mask: u8) {
+    let t = mask & (a.0 ^ b.0);
+    a.0 ^= t;
+    b.0 ^= t;
+}
+
+#[inline(always)]
+/// Returns `0xff` if the input is nonzero, and `0x00` otherwise.
+pub(crate) fn nonzero_mask(input: u8) -> u8 {
+    // OR all bits together in the rightmost bit. If the input is zero, that means that the
+    // result of ORing all bits together will also be zero. Otherwise, it will be 1.
+    let or = input
+        | input >> 1
+        | input >> 2
+        | input >> 3
+        | input >> 4
+        | input >> 5
+        | input >> 6
+        | input >> 7;
+
+    // Extend the bits to the full byte.
+    extend_bit(or)
+}
+
 impl From<u8> for GF {
     fn from(x: u8) -> Self {
         GF(x)
@@ -147,6 +831,22 @@ impl SubAssign for GF {
     }
 }
 
+/// The method that [`GF::inverse_with`] uses to calculate the multiplicative inverse.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash, Default)]
+pub enum InverseMethod {
+    /// Multiplies the element by every element of the field, and selects the one that gives a
+    /// product of 1 with a mask. This takes 256 multiplications.
+    BruteForce,
+    /// Raises the element to the power 254 with square and multiply, since
+    /// <i>a</i><sup>255</sup> = 1 for every nonzero <i>a</i> by Fermat's little theorem.
+    Fermat,
+    /// The Itoh-Tsujii algorithm, which calculates the same power as [`InverseMethod::Fermat`]
+    /// with an addition chain that needs fewer multiplications. This is the method of
+    /// [`GF::multiplicative_inverse`].
+    #[default]
+    ItohTsujii,
+}
+
 /// Multiplication in this finite field is multiplication modulo AES standardized irreducible
 /// polynomial
 /// <i>x</i><sup>8</sup> + <i>x</i><sup>4</sup> + <i>x</i><sup>3</sup> + <i>x</i> + 1
@@ -155,45 +855,43 @@ impl Mul for GF {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let mut a = self.0;
-        let mut b = rhs.0;
-
-        let mut p = 0;
-
-        // Implementation details from https://en.wikipedia.org/wiki/Finite_field_arithmetic
-        // Run the following loop eight times (once per bit).
-        for _ in 0..8 {
-            // If the rightmost bit of b is set, exclusive OR the product p by the value of a.
-            // This is polynomial addition.
-            p ^= extend_bit(b & 1) & a;
-
-            // Shift b one bit to the right, discarding the rightmost bit, and making the leftmost
-            // bit have a value of zero. This divides the polynomial by x, discarding the x0 term.
-            b >>= 1;
+        self.mul_const(rhs)
+    }
+}
 
-            // Keep track of whether the leftmost bit of a is set to one and call this value carry.
-            let carry = (a >> 7) & 1;
+impl MulAssign for GF {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.mul(rhs)
+    }
+}
 
-            // Shift a one bit to the left, discarding the leftmost bit, and making the new
-            // rightmost bit zero. This multiplies the polynomial by x, but we still need to take
-            // account of carry which represented the coefficient of x7.
-            a <<= 1;
+/// Dividing by an element is equal to multiplying by its multiplicative inverse.
+/// Since `GF(0)` has no multiplicative inverse, dividing by `GF(0)` results in `GF(0)`.
+impl Div for GF {
+    type Output = Self;
 
-            // If carry had a value of one, exclusive or a with the hexadecimal
-            // number 0x1b (00011011 in binary). 0x1b corresponds to the irreducible polynomial with
-            // the high term eliminated. Conceptually, the high term of the irreducible polynomial
-            // and carry add modulo 2 to 0.
-            a ^= extend_bit(carry & 1) & 0x1b;
-        }
+    fn div(self, rhs: Self) -> Self::Output {
+        #[allow(clippy::suspicious_arithmetic_impl)]
+        self.mul(rhs.multiplicative_inverse())
+    }
+}
 
-        // p now has the product
-        GF(p)
+impl DivAssign for GF {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = self.div(rhs)
     }
 }
 
-impl MulAssign for GF {
-    fn mul_assign(&mut self, rhs: Self) {
-        *self = self.mul(rhs)
+/// A xorshift generator for the tests of all modules, which is fine for tests but not for real
+/// secrets.
+#[cfg(test)]
+pub(crate) fn test_rng() -> impl FnMut() -> u8 {
+    let mut state = 0x2545_f491u32;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as u8
     }
 }
 
@@ -212,19 +910,44 @@ mod tests {
         assert_eq!(GF(0xff).multiplicative_inverse(), GF(0x1c));
     }
 
+    #[test]
+    fn inverse_methods_agree() {
+        for x in 0u8..=255u8 {
+            let inverse = GF(x).inverse_with(InverseMethod::BruteForce);
+            assert_eq!(GF(x).inverse_with(InverseMethod::Fermat), inverse);
+            assert_eq!(GF(x).inverse_with(InverseMethod::ItohTsujii), inverse);
+            assert_eq!(GF(x).inverse_with(InverseMethod::default()), inverse);
+        }
+    }
+
+    #[test]
+    fn multiplicative_inverse_is_bijection() {
+        let mut seen = [false; 256];
+        for x in 1u8..=255u8 {
+            let inverse = GF(x).multiplicative_inverse();
+            assert_ne!(inverse, GF(0));
+            assert!(!seen[inverse.0 as usize]);
+            seen[inverse.0 as usize] = true;
+
+            assert_eq!(GF(x) * inverse, GF(1));
+            assert_eq!(inverse.multiplicative_inverse(), GF(x));
+        }
+        assert_eq!(GF(0).multiplicative_inverse(), GF(0));
+    }
+
     #[test]
     fn test_shift_behaviour() {
         let mut x: i8 = 1;
         x <<= 7;
-        assert_eq!(x as u8, 0b1000_0000 as u8);
+        assert_eq!(x as u8, 0b1000_0000_u8);
         x = x.wrapping_shr(7);
         assert_eq!(x as u8, 0b1111_1111);
 
         let mut x: i8 = 0;
         x <<= 7;
-        assert_eq!(x as u8, 0b0000_0000 as u8);
+        assert_eq!(x as u8, 0b0000_0000_u8);
         x = x.wrapping_shr(7);
-        assert_eq!(x as u8, 0b0000_0000 as u8);
+        assert_eq!(x as u8, 0b0000_0000_u8);
     }
 
     #[test]
@@ -238,6 +961,196 @@ mod tests {
         assert_eq!(extend_bit(0b1100_0101), 0xff);
     }
 
+    #[test]
+    fn test_nonzero_mask() {
+        assert_eq!(nonzero_mask(0), 0x00);
+        assert_eq!(GF(0).nonzero_mask(), 0x00);
+        for x in 1u8..=255u8 {
+            assert_eq!(nonzero_mask(x), 0xff);
+            assert_eq!(GF(x).nonzero_mask(), 0xff);
+        }
+    }
+
+    #[test]
+    fn test_hamming_weight() {
+        assert_eq!(GF(0x00).hamming_weight(), 0);
+        assert_eq!(GF(0x01).hamming_weight(), 1);
+        assert_eq!(GF(0x1b).hamming_weight(), 4);
+        assert_eq!(GF(0xff).hamming_weight(), 8);
+    }
+
+    #[test]
+    fn test_parity() {
+        assert_eq!(GF(0x00).parity(), 0);
+        assert_eq!(GF(0xff).parity(), 0);
+        for x in 0u8..=255u8 {
+            assert_eq!(GF(x).parity() as u32, GF(x).hamming_weight() % 2);
+        }
+    }
+
+    #[test]
+    fn exp_generator_matches_pow() {
+        for n in 0u8..=255u8 {
+            assert_eq!(GF::exp_generator(n), GF::GENERATOR.pow(n as u32));
+        }
+    }
+
+    #[test]
+    fn discrete_log_inverts_exp_generator() {
+        for x in 1u8..=255u8 {
+            let log = GF(x).discrete_log().unwrap();
+            assert!(log < 255);
+            assert_eq!(GF::exp_generator(log), GF(x));
+        }
+        assert_eq!(GF(1).discrete_log(), Some(0));
+        assert_eq!(GF(0).discrete_log(), None);
+    }
+
+    #[test]
+    fn log_table_matches_discrete_log() {
+        let table = GF::compute_log_table();
+        for x in 1u8..=255u8 {
+            assert_eq!(GF::GENERATOR.pow(u32::from(table[x as usize])), GF(x));
+            assert_eq!(Some(table[x as usize]), GF(x).discrete_log());
+        }
+        assert_eq!(table[0], 0);
+        assert_eq!(table[1], 0);
+    }
+
+    #[test]
+    fn inverse_table_matches_inverse() {
+        let table = GF::inverse_table();
+        for x in 0u8..=255u8 {
+            assert_eq!(table[x as usize], GF(x).multiplicative_inverse());
+        }
+        assert_eq!(table[0], GF(0));
+    }
+
+    #[test]
+    fn new_is_tuple_constructor() {
+        const ELEMENT: GF = GF::new(83);
+        assert_eq!(ELEMENT, GF(83));
+        for x in 0u8..=255u8 {
+            assert_eq!(GF::new(x), GF(x));
+        }
+    }
+
+    #[test]
+    fn checked_from_in_const_context() {
+        const TABLE: [GF; 3] = [
+            GF::checked_from(0),
+            GF::checked_from(0x1b),
+            GF::checked_from(0xff),
+        ];
+        assert_eq!(TABLE, [GF(0), GF(0x1b), GF(0xff)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn checked_from_out_of_range() {
+        let x = core::hint::black_box(0x100);
+        GF::checked_from(x);
+    }
+
+    #[test]
+    fn test_trace() {
+        for a in 0u8..=255u8 {
+            // Of the polynomial basis, only x^5 and x^7 have trace 1.
+            assert_eq!(GF(a).trace(), ((a >> 5) ^ (a >> 7)) & 1);
+            assert_eq!((GF(a) * GF(a)).trace(), GF(a).trace());
+        }
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_casts() {
+        let elements = [GF(0x53), GF(0xca), GF(0x00), GF(0xff)];
+        let bytes: [u8; 4] = bytemuck::cast(elements);
+        assert_eq!(bytes, [0x53, 0xca, 0x00, 0xff]);
+        assert_eq!(bytemuck::cast::<[u8; 4], [GF; 4]>(bytes), elements);
+
+        let slice: &[u8] = bytemuck::cast_slice(&elements[..]);
+        assert_eq!(slice, &bytes[..]);
+        assert_eq!(bytemuck::cast_slice::<u8, GF>(slice), &elements[..]);
+        assert_eq!(<GF as bytemuck::Zeroable>::zeroed(), GF(0));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_conjugates() {
+        assert_eq!(GF(0).conjugates(), [GF(0)]);
+        assert_eq!(GF(1).conjugates(), [GF(1)]);
+
+        let conjugates = GF::GENERATOR.conjugates();
+        assert_eq!(conjugates.len(), 8);
+        for (i, &c) in conjugates.iter().enumerate() {
+            assert_eq!(c, GF::GENERATOR.pow(1 << i));
+            assert!(conjugates[..i].iter().all(|&d| d != c));
+        }
+
+        for a in 0u8..=255u8 {
+            let conjugates = GF(a).conjugates();
+            assert_eq!(8 % conjugates.len(), 0);
+            assert!(conjugates.iter().all(|&c| c.conjugates().contains(&GF(a))));
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_is_conjugate_to() {
+        assert!(GF(2).is_conjugate_to(GF(2) * GF(2)));
+        assert!(GF(2).is_conjugate_to(GF(2)));
+        assert!(!GF(2).is_conjugate_to(GF(1)));
+        assert!(!GF(0).is_conjugate_to(GF(1)));
+
+        for a in (0u8..=255u8).map(GF) {
+            let conjugates = a.conjugates();
+            for b in (0u8..=255u8).map(GF) {
+                assert_eq!(a.is_conjugate_to(b), conjugates.contains(&b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_ct_gt() {
+        for a in 0u8..=255u8 {
+            for b in (0u8..=255u8).step_by(7).chain([a, a.wrapping_add(1), 255]) {
+                let expected = if a > b { 0xff } else { 0x00 };
+                assert_eq!(GF(a).ct_gt(GF(b)), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pow_signed() {
+        for x in (0u8..=255u8).map(GF) {
+            let inverse = x.multiplicative_inverse();
+            assert_eq!(x.pow_signed(-1), inverse);
+            assert_eq!(x.pow_signed(-2), inverse * inverse);
+            assert_eq!(x.pow_signed(0), GF(1));
+            assert_eq!(x.pow_signed(7), x.pow(7));
+            assert_eq!(x.pow_signed(i32::MIN), inverse.pow(1 << 31));
+        }
+        assert_eq!(GF(0).pow_signed(-3), GF(0));
+    }
+
+    #[test]
+    fn test_pow2() {
+        for n in (0..600).chain([u32::MAX - 1, u32::MAX]) {
+            assert_eq!(GF::pow2(n), GF(2).pow(n));
+        }
+    }
+
+    #[test]
+    fn test_trace_dual_basis() {
+        let dual = GF::trace_dual_basis();
+        for i in 0..8 {
+            for (j, &d) in dual.iter().enumerate() {
+                assert_eq!((GF(2).pow(i as u32) * d).trace(), (i == j) as u8);
+            }
+        }
+    }
+
     #[test]
     fn multiplication_example_wikipedia() {
         let mut x = GF(0x53);
@@ -265,6 +1178,131 @@ mod tests {
         assert_eq!(x, GF(0xfe))
     }
 
+    #[test]
+    fn pow_matches_repeated_multiplication() {
+        for x in [0x00, 0x01, 0x02, 0x03, 0x53, 0xca, 0xff] {
+            let mut expected = GF(1);
+            for exp in 0..600 {
+                assert_eq!(GF(x).pow(exp), expected);
+                expected *= GF(x);
+            }
+        }
+    }
+
+    #[test]
+    fn pow_of_group_order() {
+        for x in 1u8..=255u8 {
+            assert_eq!(GF(x).pow(255), GF(1));
+        }
+        assert_eq!(GF(0).pow(0), GF(1));
+        assert_eq!(GF(0).pow(255), GF(0));
+    }
+
+    #[test]
+    fn generator_has_full_order() {
+        // 255 = 3 * 5 * 17, so the order is 255 if no power 255 / p is one.
+        for p in [3, 5, 17] {
+            assert_ne!(GF::GENERATOR.pow(255 / p), GF(1));
+        }
+        assert_eq!(GF(2).pow(51), GF(1));
+    }
+
+    #[test]
+    fn test_multiplicative_order() {
+        let mut count = [0usize; 256];
+        for x in 1u8..=255u8 {
+            let order = GF(x).multiplicative_order();
+            assert_eq!(GF(x).pow(u32::from(order)), GF(1));
+            for d in 1..order {
+                assert_ne!(GF(x).pow(u32::from(d)), GF(1));
+            }
+            count[order as usize] += 1;
+        }
+        assert_eq!(GF(0).multiplicative_order(), 0);
+        // A cyclic group of order 255 has phi(d) elements of order d.
+        assert_eq!(count[255], 128);
+        assert_eq!(count[51], 32);
+        assert_eq!(count[1], 1);
+    }
+
+    #[test]
+    fn test_subgroup_generator() {
+        for order in [1u16, 3, 5, 15, 17, 51, 85, 255] {
+            let root = GF::subgroup_generator(order).unwrap();
+            assert_eq!(root.multiplicative_order(), order);
+        }
+        for order in [0u16, 2, 7, 16, 254, 256, 510, u16::MAX] {
+            assert_eq!(GF::subgroup_generator(order), None);
+        }
+    }
+
+    #[test]
+    fn pow_ct_matches_pow() {
+        let exponents = (0..300).chain([0x8000_0000, 0xdead_beef, u32::MAX - 1, u32::MAX]);
+        for exp in exponents {
+            for x in 0u8..=255u8 {
+                assert_eq!(GF(x).pow_ct(exp), GF(x).pow(exp));
+            }
+        }
+    }
+
+    #[test]
+    fn test_conditional_swap() {
+        let (mut a, mut b) = (GF(0x53), GF(0xca));
+        conditional_swap(&mut a, &mut b, 0x00);
+        assert_eq!((a, b), (GF(0x53), GF(0xca)));
+        conditional_swap(&mut a, &mut b, 0xff);
+        assert_eq!((a, b), (GF(0xca), GF(0x53)));
+    }
+
+    #[test]
+    fn division_inverts_multiplication() {
+        for x in 0u8..=255u8 {
+            for y in [0x01, 0x02, 0x53, 0xca, 0xff] {
+                assert_eq!((GF(x) * GF(y)) / GF(y), GF(x));
+            }
+        }
+
+        let mut x = GF(0x57);
+        x /= GF(0x83);
+        assert_eq!(x * GF(0x83), GF(0x57));
+    }
+
+    #[test]
+    fn division_by_zero() {
+        assert_eq!(GF(0x53) / GF(0), GF(0));
+    }
+
+    /// Checking all 256^3 triples takes too long, so the first two operands range over the
+    /// whole field and the third operand over this sample. It contains zero, one, every single
+    /// bit that triggers a reduction when shifted, and a few arbitrary dense elements.
+    const THIRD_OPERANDS: [u8; 8] = [0x00, 0x01, 0x02, 0x80, 0x1b, 0x53, 0xca, 0xff];
+
+    #[test]
+    fn multiplication_distributes_over_addition() {
+        for a in 0u8..=255u8 {
+            for b in 0u8..=255u8 {
+                for c in THIRD_OPERANDS {
+                    let (a, b, c) = (GF(a), GF(b), GF(c));
+                    assert_eq!(a * (b + c), a * b + a * c);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn multiplication_is_associative_and_commutative() {
+        for a in 0u8..=255u8 {
+            for b in 0u8..=255u8 {
+                assert_eq!(GF(a) * GF(b), GF(b) * GF(a));
+                for c in THIRD_OPERANDS {
+                    let (a, b, c) = (GF(a), GF(b), GF(c));
+                    assert_eq!((a * b) * c, a * (b * c));
+                }
+            }
+        }
+    }
+
     #[test]
     fn zero_addition() {
         assert_eq!(GF(0x53) + GF(0x0), GF(0x53));
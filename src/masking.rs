@@ -0,0 +1,175 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Boolean masking of field elements, as a countermeasure against side-channel attacks.
+//!
+//! A secret element is split into `d` shares whose sum (XOR) is the secret. Every `d - 1` shares
+//! are independent of the secret, so an attacker has to combine the leakage of all shares.
+//! Sums of masked elements are computed share by share, and products with [`masked_mul`].
+
+use crate::{GFError, GF};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Splits `secret` into `shares` random shares, whose sum is the secret. The first `shares - 1`
+/// shares are taken from `rng`, which must be a cryptographically secure random number
+/// generator.
+///
+/// Returns [`GFError::OutOfRange`] if `shares` is zero.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, masking::{mask, unmask}};
+/// # let mut state = 0x2545_f491u32;
+/// # let rng = || { state ^= state << 13; state ^= state >> 17; state ^= state << 5; state as u8 };
+/// let shares = mask(GF(0x53), 3, rng).unwrap();
+///
+/// assert_eq!(shares.len(), 3);
+/// assert_eq!(unmask(&shares), GF(0x53));
+/// ```
+pub fn mask(secret: GF, shares: usize, mut rng: impl FnMut() -> u8) -> Result<Vec<GF>, GFError> {
+    if shares == 0 {
+        return Err(GFError::OutOfRange);
+    }
+
+    let mut masked = vec![GF(0); shares];
+    let mut last = secret;
+    for share in &mut masked[1..] {
+        *share = GF(rng());
+        last += *share;
+    }
+    masked[0] = last;
+
+    Ok(masked)
+}
+
+/// Combines the shares into the secret, by adding all of them.
+pub fn unmask(shares: &[GF]) -> GF {
+    shares.iter().fold(GF(0), |acc, &share| acc + share)
+}
+
+/// Multiplies two masked elements without combining their shares, with the secure
+/// multiplication gadget of Ishai, Sahai and Wagner (ISW). The sum of the output shares is the
+/// product of the secrets, and the output has as many shares as the inputs.
+///
+/// Every pair of shares `i < j` gets a fresh random element <i>r</i><sub>ij</sub> from `rng`,
+/// for `d * (d - 1) / 2` random bytes in total. The cross products
+/// <i>a</i><sub>i</sub><i>b</i><sub>j</sub> are only ever added to a random element, in the
+/// order that is prescribed by the gadget, which gives security against probing attacks of
+/// order `d - 1`.
+///
+/// Returns [`GFError::DimensionMismatch`] if the inputs have a different number of shares.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, masking::{mask, masked_mul, unmask}};
+/// # let mut state = 0x2545_f491u32;
+/// # let mut rng = || { state ^= state << 13; state ^= state >> 17; state ^= state << 5; state as u8 };
+/// let a = mask(GF(0x53), 3, &mut rng).unwrap();
+/// let b = mask(GF(0xca), 3, &mut rng).unwrap();
+/// let product = masked_mul(&a, &b, &mut rng).unwrap();
+///
+/// assert_eq!(unmask(&product), GF(0x01));
+/// ```
+pub fn masked_mul(
+    a_shares: &[GF],
+    b_shares: &[GF],
+    mut rng: impl FnMut() -> u8,
+) -> Result<Vec<GF>, GFError> {
+    if a_shares.len() != b_shares.len() {
+        return Err(GFError::DimensionMismatch);
+    }
+
+    let d = a_shares.len();
+    let mut c: Vec<GF> = a_shares
+        .iter()
+        .zip(b_shares)
+        .map(|(&a, &b)| a * b)
+        .collect();
+
+    for i in 0..d {
+        for j in i + 1..d {
+            let r = GF(rng());
+            // The brackets matter: the random element has to be added before the second
+            // cross product, otherwise a_i * b_j + a_j * b_i could leak.
+            let r_ji = (r + a_shares[i] * b_shares[j]) + a_shares[j] * b_shares[i];
+            c[i] += r;
+            c[j] += r_ji;
+        }
+    }
+
+    Ok(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_rng;
+
+    #[test]
+    fn mask_and_unmask() {
+        let mut rng = test_rng();
+        for shares in 1..6 {
+            for x in [0x00, 0x01, 0x53, 0xff] {
+                assert_eq!(unmask(&mask(GF(x), shares, &mut rng).unwrap()), GF(x));
+            }
+        }
+        assert_eq!(mask(GF(1), 0, &mut rng), Err(GFError::OutOfRange));
+    }
+
+    #[test]
+    fn masked_product_is_product() {
+        let mut rng = test_rng();
+        for shares in 1..6 {
+            for (a, b) in [(0x53, 0xca), (0x57, 0x83), (0x00, 0x13), (0xff, 0xff)] {
+                for _ in 0..10 {
+                    let a_shares = mask(GF(a), shares, &mut rng).unwrap();
+                    let b_shares = mask(GF(b), shares, &mut rng).unwrap();
+                    let product = masked_mul(&a_shares, &b_shares, &mut rng).unwrap();
+
+                    assert_eq!(product.len(), shares);
+                    assert_eq!(unmask(&product), GF(a) * GF(b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn masked_mul_uses_fresh_randomness() {
+        let mut count = 0;
+        let rng = || {
+            count += 1;
+            0
+        };
+        let shares = [GF(1); 4];
+        masked_mul(&shares, &shares, rng).unwrap();
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn masked_mul_share_mismatch() {
+        assert_eq!(
+            masked_mul(&[GF(1), GF(2)], &[GF(3)], test_rng()),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+}
@@ -0,0 +1,1331 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Matrices with elements in GF(2<sup>8</sup>).
+
+use crate::slice::linear_combination;
+use crate::{GFError, GFPoly, GF};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Index, IndexMut, Mul};
+
+/// The default block size of the blocked matrix multiplication. Square blocks of this size fit
+/// comfortably in the L1 cache.
+const BLOCK_SIZE: usize = 64;
+
+/// Matrix with elements in the Galois field GF(2<sup>8</sup>), stored in row-major order.
+///
+/// Elements are accessed by `(row, column)` index.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, GFMatrix};
+/// let a = GFMatrix::from_fn(2, 2, |i, j| GF((i * 2 + j) as u8));
+/// let product = &a * &GFMatrix::identity(2);
+///
+/// assert_eq!(product, a);
+/// assert_eq!(product[(1, 0)], GF(2));
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct GFMatrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<GF>,
+}
+
+impl GFMatrix {
+    /// Creates a matrix in which every element is `GF(0)`.
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        GFMatrix {
+            rows,
+            cols,
+            data: vec![GF(0); rows * cols],
+        }
+    }
+
+    /// Creates the `n` by `n` identity matrix.
+    pub fn identity(n: usize) -> Self {
+        GFMatrix::from_fn(n, n, |i, j| if i == j { GF(1) } else { GF(0) })
+    }
+
+    /// Creates a matrix by calling `f(row, column)` for every element.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> GF) -> Self {
+        let mut data = Vec::with_capacity(rows * cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                data.push(f(i, j));
+            }
+        }
+        GFMatrix { rows, cols, data }
+    }
+
+    /// Creates the Moore matrix of `xs` with `rows` rows, in which the element at `(i, j)` is
+    /// `xs[j]` to the power 2<sup><i>i</i></sup>, the [Frobenius
+    /// automorphism](crate::galois::Automorphism) applied `i` times. Moore matrices are the
+    /// generator matrices of Gabidulin codes in the rank metric.
+    ///
+    /// A square Moore matrix is invertible exactly when the elements of `xs` are linearly
+    /// independent over GF(2), just like a Vandermonde matrix is invertible when its points
+    /// are distinct.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix};
+    /// let m = GFMatrix::moore(&[GF(1), GF(2), GF(4)], 3);
+    /// assert_eq!(m[(2, 1)], GF(2).pow(4));
+    /// assert!(m.inverse().is_ok());
+    ///
+    /// // GF(3) = GF(1) + GF(2), so the elements are dependent.
+    /// assert!(GFMatrix::moore(&[GF(1), GF(2), GF(3)], 3).inverse().is_err());
+    /// ```
+    pub fn moore(xs: &[GF], rows: usize) -> Self {
+        let mut data = Vec::with_capacity(rows * xs.len());
+        let mut row = xs.to_vec();
+        for _ in 0..rows {
+            data.extend_from_slice(&row);
+            for x in &mut row {
+                *x *= *x;
+            }
+        }
+        GFMatrix {
+            rows,
+            cols: xs.len(),
+            data,
+        }
+    }
+
+    /// Creates a uniformly random invertible `n` by `n` matrix, for testing decoders and other
+    /// code that needs full-rank matrices. The elements are taken from `rng`, like the random
+    /// coefficients in [`shamir`](crate::shamir).
+    ///
+    /// Random matrices are generated until one of them is invertible, which is a rejection
+    /// sampling of the invertible matrices. A random matrix is singular with a probability of
+    /// less than 1 / 255, so this almost always takes a single attempt.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GFMatrix;
+    /// # let mut state = 0x2545_f491u32;
+    /// # let rng = || { state ^= state << 13; state ^= state >> 17; state ^= state << 5; state as u8 };
+    /// let a = GFMatrix::random_invertible(4, rng);
+    ///
+    /// assert_eq!(&a * &a.inverse().unwrap(), GFMatrix::identity(4));
+    /// ```
+    pub fn random_invertible(n: usize, mut rng: impl FnMut() -> u8) -> Self {
+        loop {
+            let a = GFMatrix::from_fn(n, n, |_, _| GF(rng()));
+            if a.inverse().is_ok() {
+                return a;
+            }
+        }
+    }
+
+    /// Creates a matrix from a buffer of bytes in row-major order, so the element at
+    /// `(i, j)` is `GF(bytes[i * cols + j])`.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the length of `bytes` is not equal to
+    /// `rows * cols`.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix};
+    /// let a = GFMatrix::from_row_major(2, 3, &[1, 2, 3, 4, 5, 6]).unwrap();
+    ///
+    /// assert_eq!(a[(1, 0)], GF(4));
+    /// assert_eq!(a.to_bytes(), vec![1, 2, 3, 4, 5, 6]);
+    /// ```
+    pub fn from_row_major(rows: usize, cols: usize, bytes: &[u8]) -> Result<Self, GFError> {
+        if bytes.len() != rows * cols {
+            return Err(GFError::DimensionMismatch);
+        }
+        Ok(GFMatrix {
+            rows,
+            cols,
+            data: bytes.iter().map(|&b| GF(b)).collect(),
+        })
+    }
+
+    /// The elements of the matrix as bytes in row-major order. This is the inverse of
+    /// [`GFMatrix::from_row_major`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.data.iter().map(|x| x.0).collect()
+    }
+
+    /// The number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The elements of row `i`.
+    ///
+    /// # Panics
+    /// Panics if `i` is out of bounds.
+    pub fn row(&self, i: usize) -> &[GF] {
+        assert!(i < self.rows, "row index out of bounds");
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// The transpose of the matrix, which swaps the rows and the columns.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix};
+    /// let a = GFMatrix::from_fn(2, 3, |i, j| GF((i * 3 + j) as u8));
+    /// let t = a.transpose();
+    ///
+    /// assert_eq!(t.rows(), 3);
+    /// assert_eq!(t[(2, 1)], a[(1, 2)]);
+    /// ```
+    pub fn transpose(&self) -> GFMatrix {
+        GFMatrix::from_fn(self.cols, self.rows, |i, j| self[(j, i)])
+    }
+
+    /// The matrix made up of the given rows of this matrix, in the given order.
+    ///
+    /// # Panics
+    /// Panics if a row index is out of bounds.
+    pub fn select_rows(&self, rows: &[usize]) -> GFMatrix {
+        GFMatrix::from_fn(rows.len(), self.cols, |i, j| self[(rows[i], j)])
+    }
+
+    /// Calculates the parity-check matrix <i>H</i> = [<i>P</i><sup>T</sup> | <i>I</i>] of a
+    /// code from its systematic generator matrix <i>G</i> = [<i>I</i> | <i>P</i>], which has one
+    /// row per data symbol. Every codeword <i>c</i> = <i>m</i><i>G</i> then satisfies
+    /// <i>H</i><i>c</i><sup>T</sup> = 0. In characteristic 2, -<i>P</i><sup>T</sup> is the same
+    /// as <i>P</i><sup>T</sup>.
+    ///
+    /// The generator matrix of [`ReedSolomon`](crate::ReedSolomon) has one column per data
+    /// symbol instead, so it has to be transposed first.
+    ///
+    /// # Panics
+    /// Panics if the matrix has more rows than columns, or if its first columns don't form the
+    /// identity matrix.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix};
+    /// let g = GFMatrix::from_row_major(2, 4, &[1, 0, 3, 4, 0, 1, 5, 6]).unwrap();
+    /// let h = g.parity_check_from_systematic();
+    ///
+    /// assert_eq!(h, GFMatrix::from_row_major(2, 4, &[3, 5, 1, 0, 4, 6, 0, 1]).unwrap());
+    /// assert_eq!(&g * &h.transpose(), GFMatrix::zero(2, 2));
+    /// ```
+    pub fn parity_check_from_systematic(&self) -> GFMatrix {
+        let (k, n) = (self.rows, self.cols);
+        assert!(
+            k <= n,
+            "a generator matrix can't have more rows than columns"
+        );
+        for i in 0..k {
+            for j in 0..k {
+                assert_eq!(
+                    self[(i, j)],
+                    GF((i == j) as u8),
+                    "the generator matrix must be in systematic form"
+                );
+            }
+        }
+
+        GFMatrix::from_fn(n - k, n, |i, j| {
+            if j < k {
+                self[(j, k + i)]
+            } else {
+                GF((j - k == i) as u8)
+            }
+        })
+    }
+
+    /// Calculates a generator matrix of the dual code, which consists of the vectors that are
+    /// orthogonal to every row of the matrix. The rows of the result form a basis of the null
+    /// space of the matrix, so it is a parity-check matrix of the code that the matrix generates.
+    ///
+    /// The matrix is reduced to reduced row echelon form, and every column without a pivot gives
+    /// one basis vector. The rows don't have to be independent, so the result has <i>n</i> -
+    /// <i>r</i> rows for a matrix of rank <i>r</i> with <i>n</i> columns. For a systematic
+    /// generator matrix, the result is the same as [`parity_check_from_systematic`].
+    ///
+    /// [`parity_check_from_systematic`]: GFMatrix::parity_check_from_systematic
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix};
+    /// let g = GFMatrix::from_row_major(2, 4, &[1, 2, 3, 4, 2, 4, 6, 9]).unwrap();
+    /// let h = g.dual_code_generator();
+    ///
+    /// assert_eq!(h.rows(), 2);
+    /// assert_eq!(&g * &h.transpose(), GFMatrix::zero(2, 2));
+    /// ```
+    pub fn dual_code_generator(&self) -> GFMatrix {
+        let (a, pivots) = self.reduced_row_echelon();
+        let free: Vec<usize> = (0..self.cols).filter(|c| !pivots.contains(c)).collect();
+
+        // Setting free variable f to 1 and the others to 0 determines the pivot variables, and
+        // -x is the same as x.
+        let mut dual = GFMatrix::zero(free.len(), self.cols);
+        for (i, &f) in free.iter().enumerate() {
+            dual[(i, f)] = GF(1);
+            for (row, &p) in pivots.iter().enumerate() {
+                dual[(i, p)] = a[(row, f)];
+            }
+        }
+        dual
+    }
+
+    /// The rank of the matrix, which is the number of linearly independent rows, and also the
+    /// number of linearly independent columns.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GFMatrix;
+    /// assert_eq!(GFMatrix::from_row_major(2, 2, &[1, 2, 2, 4]).unwrap().rank(), 1);
+    /// assert_eq!(GFMatrix::identity(3).rank(), 3);
+    /// ```
+    pub fn rank(&self) -> usize {
+        self.reduced_row_echelon().1.len()
+    }
+
+    /// Calculates a rank decomposition <i>A</i> = <i>C</i><i>F</i> of the `m` by `n` matrix
+    /// <i>A</i> with rank <i>r</i>, where <i>C</i> is `m` by <i>r</i> and <i>F</i> is
+    /// <i>r</i> by `n`. Over a field, the Smith normal form of a matrix is just the identity
+    /// matrix of size <i>r</i> padded with zeros, but the factors of the decomposition are
+    /// still useful.
+    ///
+    /// <i>F</i> consists of the nonzero rows of the reduced row echelon form of <i>A</i>, so its
+    /// rows are a basis of the row space. <i>C</i> consists of the pivot columns of <i>A</i>,
+    /// which are a basis of the column space.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GFMatrix;
+    /// let a = GFMatrix::from_row_major(3, 3, &[1, 2, 3, 2, 4, 6, 0, 1, 1]).unwrap();
+    /// let (c, f) = a.rank_decomposition();
+    ///
+    /// assert_eq!((c.cols(), f.rows()), (2, 2));
+    /// assert_eq!(&c * &f, a);
+    /// ```
+    pub fn rank_decomposition(&self) -> (GFMatrix, GFMatrix) {
+        let (a, pivots) = self.reduced_row_echelon();
+        let rank = pivots.len();
+        let c = GFMatrix::from_fn(self.rows, rank, |i, j| self[(i, pivots[j])]);
+        let f = GFMatrix::from_fn(rank, self.cols, |i, j| a[(i, j)]);
+        (c, f)
+    }
+
+    /// Reduces the matrix to reduced row echelon form with Gauss-Jordan elimination, and
+    /// returns it together with the columns of the pivots, from left to right.
+    fn reduced_row_echelon(&self) -> (GFMatrix, Vec<usize>) {
+        let mut a = self.clone();
+        let mut pivots = Vec::new();
+
+        for col in 0..self.cols {
+            let rank = pivots.len();
+            let pivot = match (rank..self.rows).find(|&row| a[(row, col)] != GF(0)) {
+                Some(pivot) => pivot,
+                None => continue,
+            };
+            a.swap_rows(pivot, rank);
+            let scale = a[(rank, col)].multiplicative_inverse();
+            a.scale_row(rank, scale);
+
+            for row in 0..self.rows {
+                let factor = a[(row, col)];
+                if row != rank && factor != GF(0) {
+                    a.add_scaled_row(row, rank, factor);
+                }
+            }
+            pivots.push(col);
+        }
+
+        (a, pivots)
+    }
+
+    /// Calculates the syndrome <i>H</i><i>r</i><sup>T</sup> of a received word `r`, with the
+    /// matrix as the parity-check matrix <i>H</i> of a linear code. It has one element per row,
+    /// and it only depends on the error that was added to a codeword, not on the codeword itself.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the length of the word is not the number of
+    /// columns.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix};
+    /// let h = GFMatrix::from_row_major(1, 3, &[1, 1, 1]).unwrap();
+    ///
+    /// assert_eq!(h.syndrome(&[GF(3), GF(5), GF(6)]).unwrap(), [GF(0)]);
+    /// assert_eq!(h.syndrome(&[GF(3), GF(5), GF(7)]).unwrap(), [GF(1)]);
+    /// ```
+    pub fn syndrome(&self, r: &[GF]) -> Result<Vec<GF>, GFError> {
+        if r.len() != self.cols {
+            return Err(GFError::DimensionMismatch);
+        }
+
+        Ok((0..self.rows)
+            .map(|i| {
+                self.row(i)
+                    .iter()
+                    .zip(r)
+                    .fold(GF(0), |sum, (&h, &x)| sum + h * x)
+            })
+            .collect())
+    }
+
+    /// Returns `true` if `r` is a codeword of the linear code with the matrix as its
+    /// parity-check matrix, which is the case exactly when its [`syndrome`](GFMatrix::syndrome)
+    /// is zero. A word with the wrong length is never a codeword.
+    ///
+    /// The whole syndrome is calculated before it is compared, so the running time only depends
+    /// on the dimensions.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix};
+    /// let g = GFMatrix::from_row_major(2, 4, &[1, 0, 3, 4, 0, 1, 5, 6]).unwrap();
+    /// let h = g.parity_check_from_systematic();
+    ///
+    /// assert!(h.check_codeword(g.row(0)));
+    /// assert!(!h.check_codeword(&[GF(1), GF(0), GF(3), GF(5)]));
+    /// ```
+    pub fn check_codeword(&self, r: &[GF]) -> bool {
+        match self.syndrome(r) {
+            Ok(syndrome) => syndrome.iter().fold(0, |acc, s| acc | s.0) == 0,
+            Err(_) => false,
+        }
+    }
+
+    /// Returns `true` if every square submatrix is invertible, which makes the matrix Maximum
+    /// Distance Separable. A systematic generator matrix [<i>I</i> | <i>P</i>] generates an MDS
+    /// code, which recovers from any combination of erasures up to the number of parity
+    /// symbols, exactly when <i>P</i> is MDS.
+    ///
+    /// Every submatrix is checked, which takes exponential time, so this is only feasible for
+    /// small matrices.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::GFMatrix;
+    /// assert!(GFMatrix::from_row_major(2, 2, &[1, 2, 3, 4]).unwrap().is_mds());
+    /// assert!(!GFMatrix::from_row_major(2, 2, &[1, 2, 0, 4]).unwrap().is_mds());
+    /// ```
+    pub fn is_mds(&self) -> bool {
+        for size in 1..=self.rows.min(self.cols) {
+            let mut rows: Vec<usize> = (0..size).collect();
+            loop {
+                let mut cols: Vec<usize> = (0..size).collect();
+                loop {
+                    let submatrix = GFMatrix::from_fn(size, size, |i, j| self[(rows[i], cols[j])]);
+                    if submatrix.inverse().is_err() {
+                        return false;
+                    }
+                    if !next_combination(&mut cols, self.cols) {
+                        break;
+                    }
+                }
+                if !next_combination(&mut rows, self.rows) {
+                    break;
+                }
+            }
+        }
+        true
+    }
+
+    /// Calculates the inverse of a square matrix with Gauss-Jordan elimination.
+    ///
+    /// Every pivot is inverted once, after which its row is scaled with multiplications, so an
+    /// <i>n</i> &times; <i>n</i> matrix costs exactly <i>n</i> calls to
+    /// [`GF::multiplicative_inverse`]. The pivots depend on each other, so they can't be
+    /// inverted together, but [`slice::batch_inverse`](crate::slice::batch_inverse) inverts many
+    /// independent elements with a single inversion.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the matrix is not square, and
+    /// [`GFError::SingularMatrix`] if it has no inverse.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix};
+    /// let a = GFMatrix::from_row_major(2, 2, &[1, 2, 3, 4]).unwrap();
+    /// let inverse = a.inverse().unwrap();
+    ///
+    /// assert_eq!(&a * &inverse, GFMatrix::identity(2));
+    /// ```
+    pub fn inverse(&self) -> Result<GFMatrix, GFError> {
+        if self.rows != self.cols {
+            return Err(GFError::DimensionMismatch);
+        }
+
+        let n = self.rows;
+        let mut a = self.clone();
+        let mut inverse = GFMatrix::identity(n);
+
+        for col in 0..n {
+            let pivot = (col..n)
+                .find(|&row| a[(row, col)] != GF(0))
+                .ok_or(GFError::SingularMatrix)?;
+            a.swap_rows(pivot, col);
+            inverse.swap_rows(pivot, col);
+
+            let scale = a[(col, col)].multiplicative_inverse();
+            a.scale_row(col, scale);
+            inverse.scale_row(col, scale);
+
+            for row in 0..n {
+                let factor = a[(row, col)];
+                if row != col && factor != GF(0) {
+                    a.add_scaled_row(row, col, factor);
+                    inverse.add_scaled_row(row, col, factor);
+                }
+            }
+        }
+
+        Ok(inverse)
+    }
+
+    /// Calculates the LU decomposition of a square matrix with partial pivoting. It returns the
+    /// unit lower triangular matrix <i>L</i>, the upper triangular matrix <i>U</i> and the
+    /// permutation `p` of the rows, such that row `i` of <i>L</i><i>U</i> is row `p[i]` of the
+    /// matrix. In other words, `self.select_rows(&p)` is equal to `&l * &u`.
+    ///
+    /// The decomposition does the elimination once, after which [`GFMatrix::lu_solve`] solves
+    /// the system for any right-hand side with substitution only. This pays off when the same
+    /// matrix is used many times, such as the decoding matrix of a set of shards in every
+    /// stripe.
+    ///
+    /// Returns `None` if the matrix is singular.
+    ///
+    /// # Panics
+    /// Panics if the matrix is not square.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix};
+    /// let a = GFMatrix::from_row_major(2, 2, &[0, 2, 3, 4]).unwrap();
+    /// let (l, u, p) = a.lu().unwrap();
+    ///
+    /// assert_eq!(p, [1, 0]);
+    /// assert_eq!(a.select_rows(&p), &l * &u);
+    /// ```
+    pub fn lu(&self) -> Option<(GFMatrix, GFMatrix, Vec<usize>)> {
+        assert_eq!(self.rows, self.cols, "the matrix must be square");
+        let n = self.rows;
+        let mut u = self.clone();
+        let mut l = GFMatrix::identity(n);
+        let mut permutation: Vec<usize> = (0..n).collect();
+
+        for col in 0..n {
+            let pivot = (col..n).find(|&row| u[(row, col)] != GF(0))?;
+            u.swap_rows(pivot, col);
+            permutation.swap(pivot, col);
+            // Only the columns of L before the diagonal have been filled in.
+            for k in 0..col {
+                l.data.swap(pivot * n + k, col * n + k);
+            }
+
+            let scale = u[(col, col)].multiplicative_inverse();
+            for row in col + 1..n {
+                let factor = u[(row, col)] * scale;
+                l[(row, col)] = factor;
+                u.add_scaled_row(row, col, factor);
+            }
+        }
+
+        Some((l, u, permutation))
+    }
+
+    /// Solves <i>A</i><i>x</i> = `b` for `x`, with the decomposition `(l, u, p)` of <i>A</i>
+    /// from [`GFMatrix::lu`]. The right-hand side is permuted, and then solved for with forward
+    /// substitution through <i>L</i> and back substitution through <i>U</i>.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the dimensions of the matrices, the permutation
+    /// and `b` don't match, or if the permutation has an entry that is not a row index.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix};
+    /// let a = GFMatrix::from_row_major(2, 2, &[0, 2, 3, 4]).unwrap();
+    /// let (l, u, p) = a.lu().unwrap();
+    /// let x = GFMatrix::lu_solve(&l, &u, &p, &[GF(2), GF(7)]).unwrap();
+    ///
+    /// assert_eq!(x, [GF(1), GF(1)]);
+    /// ```
+    pub fn lu_solve(
+        l: &GFMatrix,
+        u: &GFMatrix,
+        permutation: &[usize],
+        b: &[GF],
+    ) -> Result<Vec<GF>, GFError> {
+        let n = b.len();
+        if l.rows != n || l.cols != n || u.rows != n || u.cols != n || permutation.len() != n {
+            return Err(GFError::DimensionMismatch);
+        }
+        if permutation.iter().any(|&p| p >= n) {
+            return Err(GFError::DimensionMismatch);
+        }
+
+        // Forward substitution, where L has ones on the diagonal.
+        let mut x = Vec::with_capacity(n);
+        for i in 0..n {
+            let sum = (0..i).fold(GF(0), |sum, j| sum + l[(i, j)] * x[j]);
+            x.push(b[permutation[i]] - sum);
+        }
+
+        for i in (0..n).rev() {
+            let sum = (i + 1..n).fold(GF(0), |sum, j| sum + u[(i, j)] * x[j]);
+            x[i] = (x[i] - sum) / u[(i, i)];
+        }
+        Ok(x)
+    }
+
+    /// Swaps rows `i` and `j`, which is an elementary row operation.
+    ///
+    /// # Panics
+    /// Panics if a row index is out of bounds.
+    pub fn swap_rows(&mut self, i: usize, j: usize) {
+        assert!(i < self.rows && j < self.rows, "row index out of bounds");
+        for k in 0..self.cols {
+            self.data.swap(i * self.cols + k, j * self.cols + k);
+        }
+    }
+
+    /// Multiplies row `i` by `c`, which is an elementary row operation if `c` is nonzero.
+    ///
+    /// # Panics
+    /// Panics if the row index is out of bounds.
+    pub fn scale_row(&mut self, i: usize, c: GF) {
+        assert!(i < self.rows, "row index out of bounds");
+        for x in &mut self.data[i * self.cols..(i + 1) * self.cols] {
+            *x *= c;
+        }
+    }
+
+    /// Adds `c` times row `src` to row `dst`, which is an elementary row operation if the rows
+    /// differ. Since subtraction is the same as addition, this also subtracts `c` times the
+    /// row.
+    ///
+    /// # Panics
+    /// Panics if a row index is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix};
+    /// let mut a = GFMatrix::from_row_major(2, 2, &[1, 2, 3, 4]).unwrap();
+    /// // Eliminate the 3 below the pivot.
+    /// a.add_scaled_row(1, 0, GF(3));
+    ///
+    /// assert_eq!(a.row(1), [GF(0), GF(4) + GF(3) * GF(2)]);
+    /// ```
+    pub fn add_scaled_row(&mut self, dst: usize, src: usize, c: GF) {
+        assert!(
+            dst < self.rows && src < self.rows,
+            "row index out of bounds"
+        );
+        for k in 0..self.cols {
+            let x = self.data[src * self.cols + k];
+            self.data[dst * self.cols + k] += c * x;
+        }
+    }
+
+    /// The characteristic polynomial det(<i>x</i><i>I</i> - <i>A</i>) of the square matrix, which
+    /// is monic and has the eigenvalues of the matrix as its roots.
+    ///
+    /// It is calculated with the Samuelson-Berkowitz algorithm, which doesn't divide at all, so
+    /// it works in characteristic 2, unlike the Faddeev-LeVerrier algorithm. The matrix is
+    /// peeled off one row and column at a time, and every step multiplies the characteristic
+    /// polynomial of the remaining submatrix by a Toeplitz matrix.
+    ///
+    /// # Panics
+    /// Panics if the matrix is not square.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFMatrix, GFPoly};
+    /// let a = GFMatrix::from_row_major(2, 2, &[1, 2, 3, 4]).unwrap();
+    /// let p = a.characteristic_polynomial();
+    ///
+    /// // x^2 - (1 + 4)x + (1 * 4 - 2 * 3)
+    /// assert_eq!(p, GFPoly::new(vec![GF(4) - GF(6), GF(5), GF(1)]));
+    /// assert_eq!(p.eval_matrix(&a), GFMatrix::zero(2, 2));
+    /// ```
+    pub fn characteristic_polynomial(&self) -> GFPoly {
+        assert_eq!(self.rows, self.cols, "the matrix must be square");
+        let n = self.rows;
+
+        // The coefficients of the characteristic polynomial of the submatrix with the rows and
+        // columns i.., starting with the highest degree.
+        let mut p = vec![GF(1)];
+        for i in (0..n).rev() {
+            let m = n - i;
+
+            // The first column of the Toeplitz matrix: 1, -a, -R C, -R A C, -R A^2 C, ...
+            // where a is the diagonal element, R the rest of its row, C the rest of its
+            // column and A the remaining submatrix.
+            let mut t = vec![GF(1), GF(0) - self[(i, i)]];
+            let mut v: Vec<GF> = (i + 1..n).map(|r| self[(r, i)]).collect();
+            for _ in 1..m {
+                let r_v = (i + 1..n)
+                    .zip(&v)
+                    .fold(GF(0), |acc, (c, &x)| acc + self[(i, c)] * x);
+                t.push(GF(0) - r_v);
+                v = (i + 1..n)
+                    .map(|r| {
+                        (i + 1..n)
+                            .zip(&v)
+                            .fold(GF(0), |acc, (c, &x)| acc + self[(r, c)] * x)
+                    })
+                    .collect();
+            }
+
+            p = (0..=m)
+                .map(|j| (0..=usize::min(j, m - 1)).fold(GF(0), |acc, l| acc + t[j - l] * p[l]))
+                .collect();
+        }
+
+        p.reverse();
+        GFPoly::new(p)
+    }
+
+    /// Multiplies two matrices by splitting them into square blocks of `block_size` by
+    /// `block_size` elements, which keeps the working set of the inner loops in the cache.
+    ///
+    /// The result is identical to the result of `self * rhs`.
+    ///
+    /// # Panics
+    /// Panics if the number of columns of `self` is not equal to the number of rows of `rhs`, or
+    /// if `block_size` is zero.
+    pub fn mul_blocked(&self, rhs: &GFMatrix, block_size: usize) -> GFMatrix {
+        assert_eq!(self.cols, rhs.rows, "incompatible matrix dimensions");
+        assert!(block_size > 0, "block size must be nonzero");
+
+        let mut result = GFMatrix::zero(self.rows, rhs.cols);
+        for ii in (0..self.rows).step_by(block_size) {
+            for kk in (0..self.cols).step_by(block_size) {
+                for jj in (0..rhs.cols).step_by(block_size) {
+                    let j_end = (jj + block_size).min(rhs.cols);
+                    for i in ii..(ii + block_size).min(self.rows) {
+                        let result_row = &mut result.data[i * rhs.cols..(i + 1) * rhs.cols];
+                        for k in kk..(kk + block_size).min(self.cols) {
+                            let a = self.data[i * self.cols + k];
+                            let rhs_row = &rhs.data[k * rhs.cols..(k + 1) * rhs.cols];
+                            for j in jj..j_end {
+                                result_row[j] += a * rhs_row[j];
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Multiplies two matrices without blocking. Row `i` of the product is the linear
+    /// combination of the rows of `rhs` with the coefficients in row `i` of `self`.
+    fn mul_naive(&self, rhs: &GFMatrix) -> GFMatrix {
+        assert_eq!(self.cols, rhs.rows, "incompatible matrix dimensions");
+
+        let mut result = GFMatrix::zero(self.rows, rhs.cols);
+        // An empty linear combination has no length, but the product still has columns of zeros.
+        if rhs.rows == 0 {
+            return result;
+        }
+
+        let rhs_rows: Vec<&[GF]> = (0..rhs.rows).map(|k| rhs.row(k)).collect();
+        for i in 0..self.rows {
+            let row = linear_combination(self.row(i), &rhs_rows)
+                .expect("the rows of a matrix have the same length");
+            result.data[i * rhs.cols..(i + 1) * rhs.cols].copy_from_slice(&row);
+        }
+        result
+    }
+}
+
+impl Index<(usize, usize)> for GFMatrix {
+    type Output = GF;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(i < self.rows && j < self.cols, "matrix index out of bounds");
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl IndexMut<(usize, usize)> for GFMatrix {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(i < self.rows && j < self.cols, "matrix index out of bounds");
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+/// Matrix multiplication over GF(2<sup>8</sup>). Large matrices are multiplied with
+/// [`GFMatrix::mul_blocked`].
+///
+/// # Panics
+/// Panics if the number of columns of `self` is not equal to the number of rows of `rhs`.
+impl Mul<&GFMatrix> for &GFMatrix {
+    type Output = GFMatrix;
+
+    fn mul(self, rhs: &GFMatrix) -> Self::Output {
+        if self.rows.max(self.cols).max(rhs.cols) > BLOCK_SIZE {
+            self.mul_blocked(rhs, BLOCK_SIZE)
+        } else {
+            self.mul_naive(rhs)
+        }
+    }
+}
+
+impl Mul for GFMatrix {
+    type Output = GFMatrix;
+
+    fn mul(self, rhs: GFMatrix) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+/// Advances the sorted indices to the next combination of `indices.len()` out of `n` indices
+/// in lexicographic order. Returns `false` if this was the last combination.
+fn next_combination(indices: &mut [usize], n: usize) -> bool {
+    let k = indices.len();
+    for i in (0..k).rev() {
+        if indices[i] < n - k + i {
+            indices[i] += 1;
+            for j in i + 1..k {
+                indices[j] = indices[j - 1] + 1;
+            }
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A matrix filled with elements that look random enough to catch indexing mistakes.
+    fn pseudo_random(rows: usize, cols: usize, seed: usize) -> GFMatrix {
+        GFMatrix::from_fn(rows, cols, |i, j| {
+            GF(((i * 31 + j * 17 + seed) ^ (i * j * 7)) as u8)
+        })
+    }
+
+    #[test]
+    fn identity_is_neutral() {
+        let a = pseudo_random(3, 5, 1);
+        assert_eq!(&GFMatrix::identity(3) * &a, a);
+        assert_eq!(&a * &GFMatrix::identity(5), a);
+    }
+
+    #[test]
+    fn small_multiplication() {
+        let a = GFMatrix::from_fn(2, 2, |i, j| {
+            [[GF(0x57), GF(0x01)], [GF(0x02), GF(0x03)]][i][j]
+        });
+        let b = GFMatrix::from_fn(2, 1, |i, _| [GF(0x83), GF(0x13)][i]);
+        let product = &a * &b;
+
+        assert_eq!(product[(0, 0)], GF(0x57) * GF(0x83) + GF(0x13));
+        assert_eq!(product[(1, 0)], GF(0x02) * GF(0x83) + GF(0x03) * GF(0x13));
+    }
+
+    #[test]
+    fn blocked_multiplication_matches_naive() {
+        let a = pseudo_random(100, 90, 3);
+        let b = pseudo_random(90, 110, 5);
+        let expected = a.mul_naive(&b);
+
+        for block_size in [1, 7, 32, 64, 200] {
+            assert_eq!(a.mul_blocked(&b, block_size), expected);
+        }
+        assert_eq!(&a * &b, expected);
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let a = pseudo_random(5, 3, 4);
+        assert_eq!(GFMatrix::from_row_major(5, 3, &a.to_bytes()), Ok(a));
+    }
+
+    #[test]
+    fn bytes_are_row_major() {
+        let a = GFMatrix::from_fn(2, 3, |i, j| GF((10 * i + j) as u8));
+        assert_eq!(a.to_bytes(), vec![0, 1, 2, 10, 11, 12]);
+        assert_eq!(a.row(1), &[GF(10), GF(11), GF(12)]);
+    }
+
+    #[test]
+    fn from_row_major_wrong_length() {
+        assert_eq!(
+            GFMatrix::from_row_major(2, 2, &[1, 2, 3]),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn transpose_twice_is_identity() {
+        let a = pseudo_random(4, 7, 11);
+        assert_eq!(a.transpose().transpose(), a);
+        assert_eq!(GFMatrix::zero(0, 3).transpose(), GFMatrix::zero(3, 0));
+    }
+
+    #[test]
+    fn transpose_of_product() {
+        let a = pseudo_random(3, 5, 2);
+        let b = pseudo_random(5, 4, 9);
+        assert_eq!((&a * &b).transpose(), &b.transpose() * &a.transpose());
+    }
+
+    #[test]
+    fn inverse_of_invertible_matrix() {
+        // A Vandermonde matrix with distinct points is invertible.
+        let a = GFMatrix::from_fn(5, 5, |i, j| GF(i as u8 + 1).pow(j as u32));
+        let inverse = a.inverse().unwrap();
+
+        assert_eq!(&a * &inverse, GFMatrix::identity(5));
+        assert_eq!(&inverse * &a, GFMatrix::identity(5));
+        assert_eq!(GFMatrix::identity(3).inverse(), Ok(GFMatrix::identity(3)));
+    }
+
+    /// Gauss-Jordan elimination that divides every entry of the pivot row by the pivot.
+    fn inverse_by_division(m: &GFMatrix) -> GFMatrix {
+        let n = m.rows();
+        let mut a = m.clone();
+        let mut inverse = GFMatrix::identity(n);
+        for col in 0..n {
+            let pivot = (col..n).find(|&row| a[(row, col)] != GF(0)).unwrap();
+            a.swap_rows(pivot, col);
+            inverse.swap_rows(pivot, col);
+            let p = a[(col, col)];
+            for j in 0..n {
+                a[(col, j)] /= p;
+                inverse[(col, j)] /= p;
+            }
+            for row in (0..n).filter(|&row| row != col) {
+                let factor = a[(row, col)];
+                for j in 0..n {
+                    let (a_col, inverse_col) = (a[(col, j)], inverse[(col, j)]);
+                    a[(row, j)] -= factor * a_col;
+                    inverse[(row, j)] -= factor * inverse_col;
+                }
+            }
+        }
+        inverse
+    }
+
+    #[test]
+    fn inverse_matches_elimination_by_division() {
+        let mut invertible = 0;
+        for seed in 0..10 {
+            let a = pseudo_random(6, 6, seed);
+            if let Ok(inverse) = a.inverse() {
+                assert_eq!(inverse, inverse_by_division(&a));
+                invertible += 1;
+            }
+        }
+        assert!(invertible > 0);
+    }
+
+    #[test]
+    fn elementary_row_operations() {
+        let mut a = GFMatrix::from_row_major(3, 2, &[1, 2, 3, 4, 5, 6]).unwrap();
+
+        a.swap_rows(0, 2);
+        assert_eq!(
+            a,
+            GFMatrix::from_row_major(3, 2, &[5, 6, 3, 4, 1, 2]).unwrap()
+        );
+        a.swap_rows(1, 1);
+        assert_eq!(a.row(1), [GF(3), GF(4)]);
+
+        a.scale_row(1, GF(2));
+        assert_eq!(a.row(1), [GF(6), GF(8)]);
+
+        a.add_scaled_row(0, 2, GF(5));
+        assert_eq!(a.row(0), [GF(5) + GF(5), GF(6) + GF(5) * GF(2)]);
+        assert_eq!(a.row(2), [GF(1), GF(2)]);
+    }
+
+    #[test]
+    fn row_operations_perform_elimination() {
+        // Reduce [a | I] to [I | a^-1] with only the public row operations.
+        let a = pseudo_random(4, 4, 3);
+        let mut augmented = GFMatrix::from_fn(4, 8, |i, j| {
+            if j < 4 {
+                a[(i, j)]
+            } else {
+                GF((j - 4 == i) as u8)
+            }
+        });
+        for col in 0..4 {
+            let pivot = (col..4)
+                .find(|&row| augmented[(row, col)] != GF(0))
+                .unwrap();
+            augmented.swap_rows(pivot, col);
+            let scale = augmented[(col, col)].multiplicative_inverse();
+            augmented.scale_row(col, scale);
+            for row in (0..4).filter(|&row| row != col) {
+                let factor = augmented[(row, col)];
+                augmented.add_scaled_row(row, col, factor);
+            }
+        }
+
+        let inverse = GFMatrix::from_fn(4, 4, |i, j| augmented[(i, j + 4)]);
+        assert_eq!(Ok(inverse), a.inverse());
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_operation_out_of_bounds() {
+        GFMatrix::zero(2, 2).add_scaled_row(2, 0, GF(1));
+    }
+
+    #[test]
+    fn inverse_of_singular_matrix() {
+        let a = GFMatrix::from_row_major(3, 3, &[1, 2, 3, 2, 4, 6, 7, 8, 9]).unwrap();
+        assert_eq!(a.inverse(), Err(GFError::SingularMatrix));
+        assert_eq!(
+            GFMatrix::zero(2, 3).inverse(),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn random_invertible_matrices() {
+        let mut rng = crate::test_rng();
+
+        for n in [0, 1, 2, 5, 16] {
+            let a = GFMatrix::random_invertible(n, &mut rng);
+            assert_eq!((a.rows(), a.cols()), (n, n));
+            assert_eq!(&a * &a.inverse().unwrap(), GFMatrix::identity(n));
+        }
+    }
+
+    #[test]
+    fn random_invertible_rejects_singular_matrices() {
+        // The first matrix is all zeros, which has to be rejected.
+        let mut values = [0u8; 4].iter().chain(&[1, 2, 3, 4]).copied();
+        let a = GFMatrix::random_invertible(2, || values.next().unwrap());
+        assert_eq!(a, GFMatrix::from_row_major(2, 2, &[1, 2, 3, 4]).unwrap());
+    }
+
+    #[test]
+    fn lu_decomposition_reconstructs_matrix() {
+        let mut matrices = vec![pseudo_random(6, 6, 1), GFMatrix::identity(3)];
+        // Force a zero pivot in every column to exercise the row swaps.
+        let mut a = pseudo_random(5, 5, 2);
+        for i in 0..5 {
+            a[(i, i)] = GF(0);
+        }
+        matrices.push(a);
+
+        for a in &matrices {
+            let n = a.rows();
+            let (l, u, p) = a.lu().unwrap();
+            assert_eq!(a.select_rows(&p), &l * &u);
+            for i in 0..n {
+                assert_eq!(l[(i, i)], GF(1));
+                for j in i + 1..n {
+                    assert_eq!(l[(i, j)], GF(0));
+                    assert_eq!(u[(j, i)], GF(0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lu_solve_matches_inverse() {
+        let a = pseudo_random(6, 6, 3);
+        let inverse = a.inverse().unwrap();
+        let (l, u, p) = a.lu().unwrap();
+
+        for seed in 0..5 {
+            let b: Vec<GF> = (0..6).map(|i| GF((i * 12 + seed * 7 + 1) as u8)).collect();
+            let x = GFMatrix::lu_solve(&l, &u, &p, &b).unwrap();
+            let expected = &inverse * &GFMatrix::from_fn(6, 1, |i, _| b[i]);
+            assert_eq!(GFMatrix::from_fn(6, 1, |i, _| x[i]), expected);
+        }
+        assert_eq!(
+            GFMatrix::lu_solve(&l, &u, &p, &[GF(1); 5]),
+            Err(GFError::DimensionMismatch)
+        );
+        assert_eq!(
+            GFMatrix::lu_solve(&l, &u, &[6, 0, 1, 2, 3, 4], &[GF(1); 6]),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn lu_of_singular_matrix() {
+        let a = GFMatrix::from_row_major(3, 3, &[1, 2, 3, 2, 4, 6, 5, 6, 7]).unwrap();
+        assert_eq!(a.lu(), None);
+        assert_eq!(GFMatrix::zero(2, 2).lu(), None);
+    }
+
+    #[test]
+    fn characteristic_polynomial_of_triangular_matrix() {
+        let a = GFMatrix::from_fn(4, 4, |i, j| {
+            if i <= j {
+                GF((i * 4 + j + 1) as u8)
+            } else {
+                GF(0)
+            }
+        });
+        let expected = (0..4).fold(GFPoly::monomial(GF(1), 0), |acc, i| {
+            &acc * &GFPoly::new(vec![GF(0) - a[(i, i)], GF(1)])
+        });
+        assert_eq!(a.characteristic_polynomial(), expected);
+        assert_eq!(
+            GFMatrix::zero(0, 0).characteristic_polynomial(),
+            GFPoly::monomial(GF(1), 0)
+        );
+    }
+
+    #[test]
+    fn cayley_hamilton() {
+        for n in [1, 2, 3, 5, 8] {
+            let a = pseudo_random(n, n, n);
+            let p = a.characteristic_polynomial();
+
+            assert_eq!(p.degree(), Some(n));
+            assert_eq!(p.leading_coefficient(), GF(1));
+            assert_eq!(p.eval_matrix(&a), GFMatrix::zero(n, n));
+        }
+    }
+
+    #[test]
+    fn characteristic_polynomial_of_singular_matrix() {
+        // The constant term is the determinant, which is zero for a singular matrix.
+        let a = GFMatrix::from_row_major(3, 3, &[1, 2, 3, 2, 4, 6, 7, 8, 9]).unwrap();
+        assert_eq!(a.characteristic_polynomial().coefficients()[0], GF(0));
+
+        let b = pseudo_random(4, 4, 3);
+        assert_ne!(b.characteristic_polynomial().coefficients()[0], GF(0));
+        assert!(b.inverse().is_ok());
+    }
+
+    #[test]
+    fn select_rows_in_order() {
+        let a = pseudo_random(4, 3, 6);
+        let selected = a.select_rows(&[3, 0]);
+        assert_eq!(selected.row(0), a.row(3));
+        assert_eq!(selected.row(1), a.row(0));
+    }
+
+    #[test]
+    fn parity_check_of_reed_solomon_code() {
+        let rs = crate::ReedSolomon::new(5, 3).unwrap();
+        let g = rs.generator_matrix().transpose();
+        let h = g.parity_check_from_systematic();
+
+        assert_eq!(h.rows(), 3);
+        assert_eq!(h.cols(), 8);
+        assert_eq!(&g * &h.transpose(), GFMatrix::zero(5, 3));
+
+        let data = [GF(0x53), GF(0xca), GF(0x01), GF(0x00), GF(0xff)];
+        let encoded = rs.encode(&data).unwrap();
+        let codeword = GFMatrix::from_fn(8, 1, |i, _| encoded[i]);
+        assert_eq!(&h * &codeword, GFMatrix::zero(3, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn parity_check_of_non_systematic_matrix() {
+        let g = GFMatrix::from_row_major(2, 3, &[1, 1, 2, 0, 1, 3]).unwrap();
+        let _ = g.parity_check_from_systematic();
+    }
+
+    #[test]
+    fn dual_code_is_orthogonal() {
+        for (k, n, seed) in [(3, 7, 1), (5, 5, 2), (1, 4, 3), (6, 10, 4)] {
+            let g = pseudo_random(k, n, seed);
+            let dual = g.dual_code_generator();
+            let rank = n - dual.rows();
+
+            assert_eq!(dual.cols(), n);
+            assert_eq!(&g * &dual.transpose(), GFMatrix::zero(k, dual.rows()));
+            // The dual of the dual has the same dimension as the code.
+            assert_eq!(dual.dual_code_generator().rows(), rank);
+        }
+    }
+
+    #[test]
+    fn dual_code_of_dependent_rows() {
+        let mut g = pseudo_random(4, 6, 5);
+        g.add_scaled_row(3, 0, GF(7));
+        for j in 0..6 {
+            g[(2, j)] = g[(1, j)] * GF(0x1d);
+        }
+        let dual = g.dual_code_generator();
+
+        assert_eq!(dual.rows(), 6 - 3);
+        assert_eq!(&g * &dual.transpose(), GFMatrix::zero(4, 3));
+        assert_eq!(
+            GFMatrix::zero(2, 3).dual_code_generator(),
+            GFMatrix::identity(3)
+        );
+    }
+
+    #[test]
+    fn rank_decomposition_reconstructs_matrix() {
+        let mut dependent = pseudo_random(5, 7, 6);
+        for j in 0..7 {
+            dependent[(3, j)] = dependent[(0, j)] + GF(3) * dependent[(1, j)];
+            dependent[(4, j)] = GF(0);
+        }
+        let matrices = [
+            (pseudo_random(4, 6, 1), 4),
+            (pseudo_random(6, 3, 2), 3),
+            (dependent, 3),
+            (GFMatrix::zero(3, 2), 0),
+            (GFMatrix::identity(4), 4),
+        ];
+
+        for (a, rank) in &matrices {
+            assert_eq!(a.rank(), *rank);
+            assert_eq!(a.transpose().rank(), *rank);
+
+            let (c, f) = a.rank_decomposition();
+            assert_eq!((c.rows(), c.cols()), (a.rows(), *rank));
+            assert_eq!((f.rows(), f.cols()), (*rank, a.cols()));
+            assert_eq!(&c * &f, *a);
+            assert_eq!(c.rank(), *rank);
+            assert_eq!(f.rank(), *rank);
+        }
+    }
+
+    #[test]
+    fn dual_code_of_systematic_matrix() {
+        let g = crate::ReedSolomon::new(5, 3)
+            .unwrap()
+            .generator_matrix()
+            .transpose();
+        assert_eq!(g.dual_code_generator(), g.parity_check_from_systematic());
+    }
+
+    #[test]
+    fn check_reed_solomon_codewords() {
+        let rs = crate::ReedSolomon::new(5, 3).unwrap();
+        let h = rs
+            .generator_matrix()
+            .transpose()
+            .parity_check_from_systematic();
+
+        let data = [GF(0x53), GF(0xca), GF(0x01), GF(0x00), GF(0xff)];
+        let mut codeword = rs.encode(&data).unwrap();
+        assert!(h.check_codeword(&codeword));
+        assert_eq!(h.syndrome(&codeword).unwrap(), [GF(0); 3]);
+
+        // The syndrome of a corrupted word is the syndrome of the error.
+        let mut error = [GF(0); 8];
+        error[2] = GF(0x10);
+        codeword[2] += GF(0x10);
+        assert!(!h.check_codeword(&codeword));
+        assert_eq!(h.syndrome(&codeword).unwrap(), h.syndrome(&error).unwrap());
+    }
+
+    #[test]
+    fn check_codeword_wrong_length() {
+        let h = GFMatrix::from_row_major(1, 3, &[1, 1, 1]).unwrap();
+        assert!(!h.check_codeword(&[GF(0); 2]));
+        assert_eq!(h.syndrome(&[GF(0); 4]), Err(GFError::DimensionMismatch));
+    }
+
+    #[test]
+    fn moore_matrix_elements() {
+        let xs = [GF(0x53), GF(0xca), GF(0x07)];
+        let m = GFMatrix::moore(&xs, 10);
+        let automorphism = crate::galois::Automorphism::new;
+
+        assert_eq!((m.rows(), m.cols()), (10, 3));
+        for i in 0..10 {
+            for (j, &x) in xs.iter().enumerate() {
+                assert_eq!(m[(i, j)], automorphism(i as u32).apply(x));
+            }
+        }
+        assert_eq!(GFMatrix::moore(&xs, 0), GFMatrix::zero(0, 3));
+    }
+
+    #[test]
+    fn moore_matrix_of_independent_elements_is_invertible() {
+        // The polynomial basis and the dual basis are both independent over GF(2).
+        let polynomial: Vec<GF> = (0..8).map(|i| GF(1 << i)).collect();
+        for xs in [
+            &polynomial[..],
+            &GF::trace_dual_basis()[..],
+            &[GF(0x53), GF(0xca)],
+        ] {
+            let m = GFMatrix::moore(xs, xs.len());
+            assert_eq!(&m * &m.inverse().unwrap(), GFMatrix::identity(xs.len()));
+        }
+    }
+
+    #[test]
+    fn moore_matrix_of_dependent_elements_is_singular() {
+        let dependent = [
+            vec![GF(0x53), GF(0x53)],
+            vec![GF(0x00), GF(0x01)],
+            vec![GF(0x12), GF(0x34), GF(0x12) + GF(0x34)],
+            vec![GF(1), GF(2), GF(4), GF(8), GF(0x0f)],
+        ];
+        for xs in &dependent {
+            let m = GFMatrix::moore(xs, xs.len());
+            assert_eq!(m.inverse(), Err(GFError::SingularMatrix));
+        }
+    }
+
+    #[test]
+    fn cauchy_matrix_is_mds() {
+        // a[i][j] = 1 / (x[i] + y[j]) for distinct x[i] and y[j].
+        let cauchy = GFMatrix::from_fn(4, 5, |i, j| {
+            (GF(i as u8) + GF(4 + j as u8)).multiplicative_inverse()
+        });
+        assert!(cauchy.is_mds());
+        assert!(cauchy.transpose().is_mds());
+
+        let rs = crate::ReedSolomon::new(4, 3).unwrap();
+        let parity = rs.generator_matrix().select_rows(&[4, 5, 6]);
+        assert!(parity.is_mds());
+    }
+
+    #[test]
+    fn singular_submatrix_is_not_mds() {
+        let mut a = GFMatrix::from_fn(3, 3, |i, j| {
+            (GF(i as u8) + GF(3 + j as u8)).multiplicative_inverse()
+        });
+        assert!(a.is_mds());
+
+        // The submatrix of rows 0 and 1 and columns 1 and 2 becomes [[1, 2], [2, 4]].
+        a[(0, 1)] = GF(1);
+        a[(0, 2)] = GF(2);
+        a[(1, 1)] = GF(2);
+        a[(1, 2)] = GF(4);
+        assert!(!a.is_mds());
+
+        assert!(!GFMatrix::identity(2).is_mds());
+    }
+
+    #[test]
+    #[should_panic]
+    fn incompatible_dimensions() {
+        let _ = &GFMatrix::zero(2, 3) * &GFMatrix::zero(2, 3);
+    }
+}
@@ -0,0 +1,165 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Number theoretic transforms over the multiplicative subgroups of GF(2<sup>8</sup>).
+//!
+//! The multiplicative group of GF(2<sup>8</sup>) has order 255 = 3 &middot; 5 &middot; 17, so
+//! it has a cyclic subgroup of order `n` for every divisor `n` of 255. The transform of length
+//! `n` evaluates a polynomial at all elements of that subgroup with a mixed radix Cooley-Tukey
+//! algorithm, which needs `n * (3 + 5 + 17)` multiplications at most, instead of the `n * n`
+//! multiplications of evaluating the polynomial at every point separately.
+
+use crate::{GFError, GF};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// The generator `GENERATOR^(255 / n)` of the subgroup of order `n`.
+fn root_of_unity(n: usize) -> Result<GF, GFError> {
+    if n > 255 {
+        return Err(GFError::DimensionMismatch);
+    }
+    GF::subgroup_generator(n as u16).ok_or(GFError::DimensionMismatch)
+}
+
+/// Evaluates the polynomial with the coefficients `values`, ordered from the lowest to the
+/// highest degree, at the points `root^0, root^1, ..., root^(n - 1)`, where `n` is the number
+/// of values and `root` has order `n`.
+fn transform(values: &[GF], root: GF) -> Vec<GF> {
+    let n = values.len();
+    if n == 1 {
+        return values.to_vec();
+    }
+
+    // Split the coefficients by their index modulo the smallest prime factor p of n, and
+    // transform every part with the root of the subgroup of order n / p.
+    let p = [3, 5, 17]
+        .iter()
+        .copied()
+        .find(|&p| n % p == 0)
+        .unwrap_or(n);
+    let m = n / p;
+    let parts: Vec<Vec<GF>> = (0..p)
+        .map(|r| {
+            let part: Vec<GF> = values[r..].iter().step_by(p).copied().collect();
+            transform(&part, root.pow(p as u32))
+        })
+        .collect();
+
+    // Combine the parts as X[k] = sum over r of root^(r * k) * parts[r][k mod m].
+    let mut result = vec![GF(0); n];
+    let mut root_k = GF(1);
+    for (k, x) in result.iter_mut().enumerate() {
+        let mut root_rk = GF(1);
+        for part in &parts {
+            *x += root_rk * part[k % m];
+            root_rk *= root_k;
+        }
+        root_k *= root;
+    }
+    result
+}
+
+/// The forward transform, which evaluates the polynomial with coefficients `values`, ordered
+/// from the lowest to the highest degree, at the points `ω^0, ω^1, ..., ω^(n - 1)`. Here `n` is
+/// the number of values, and `ω = GF::GENERATOR.pow(255 / n)` generates the subgroup of order
+/// `n`.
+///
+/// Returns [`GFError::DimensionMismatch`] if the number of values doesn't divide 255.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, GFPoly, ntt::ntt};
+/// let coefficients = [GF(1), GF(2), GF(3), GF(4), GF(5)];
+/// let omega = GF::GENERATOR.pow(255 / 5);
+/// let evaluations = ntt(&coefficients).unwrap();
+///
+/// let p = GFPoly::new(coefficients.to_vec());
+/// assert_eq!(evaluations[2], p.eval(omega.pow(2)));
+/// ```
+pub fn ntt(values: &[GF]) -> Result<Vec<GF>, GFError> {
+    Ok(transform(values, root_of_unity(values.len())?))
+}
+
+/// The inverse transform, which interpolates the coefficients of the polynomial from its
+/// evaluations at the points `ω^0, ω^1, ..., ω^(n - 1)`, such that `intt(&ntt(v)?)? == v`.
+///
+/// The inverse transform is usually scaled by `1 / n`, but `n` is odd, so `n` times `GF(1)` is
+/// `GF(1)` in GF(2<sup>8</sup>). The inverse is therefore just the forward transform with the
+/// root `ω^-1`.
+///
+/// Returns [`GFError::DimensionMismatch`] if the number of values doesn't divide 255.
+pub fn intt(values: &[GF]) -> Result<Vec<GF>, GFError> {
+    let root = root_of_unity(values.len())?;
+    Ok(transform(values, root.multiplicative_inverse()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GFPoly;
+
+    const SIZES: [usize; 8] = [1, 3, 5, 15, 17, 51, 85, 255];
+
+    fn values(n: usize) -> Vec<GF> {
+        (0..n).map(|i| GF((i * 37 + 11) as u8)).collect()
+    }
+
+    #[test]
+    fn roots_have_the_right_order() {
+        for n in SIZES {
+            let root = root_of_unity(n).unwrap();
+            assert_eq!(root.pow(n as u32), GF(1));
+            for d in 1..n {
+                assert_ne!(root.pow(d as u32), GF(1));
+            }
+        }
+    }
+
+    #[test]
+    fn ntt_matches_naive_evaluation() {
+        for n in SIZES {
+            let v = values(n);
+            let root = root_of_unity(n).unwrap();
+            let p = GFPoly::new(v.clone());
+
+            let expected: Vec<GF> = (0..n).map(|k| p.eval(root.pow(k as u32))).collect();
+            assert_eq!(ntt(&v), Ok(expected));
+        }
+    }
+
+    #[test]
+    fn intt_inverts_ntt() {
+        for n in SIZES {
+            let v = values(n);
+            assert_eq!(intt(&ntt(&v).unwrap()), Ok(v.clone()));
+            assert_eq!(ntt(&intt(&v).unwrap()), Ok(v));
+        }
+    }
+
+    #[test]
+    fn invalid_sizes() {
+        for n in [0, 2, 4, 7, 16, 254, 256] {
+            assert_eq!(ntt(&values(n)), Err(GFError::DimensionMismatch));
+            assert_eq!(intt(&values(n)), Err(GFError::DimensionMismatch));
+        }
+    }
+}
@@ -0,0 +1,164 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Multi-threaded versions of the bulk operations, for very large buffers.
+//!
+//! This module is only available with the `rayon` feature. The buffers are split into chunks of
+//! [`CHUNK_SIZE`] elements, which are processed in parallel on the rayon thread pool. Every
+//! element is handled independently, so the results are the same as those of the sequential
+//! functions.
+
+use crate::{slice, GFError, ReedSolomon, GF};
+use alloc::vec::Vec;
+use rayon::prelude::*;
+
+/// The number of elements that one task processes.
+pub const CHUNK_SIZE: usize = 16 * 1024;
+
+/// The parallel version of [`slice::scale`].
+///
+/// Returns [`GFError::DimensionMismatch`] if the slices have different lengths.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, parallel};
+/// let input = vec![GF(3); 100_000];
+/// let mut output = vec![GF(0); 100_000];
+/// parallel::scale(GF(2), &input, &mut output).unwrap();
+///
+/// assert!(output.iter().all(|&o| o == GF(6)));
+/// ```
+pub fn scale(coefficient: GF, input: &[GF], output: &mut [GF]) -> Result<(), GFError> {
+    if input.len() != output.len() {
+        return Err(GFError::DimensionMismatch);
+    }
+
+    output
+        .par_chunks_mut(CHUNK_SIZE)
+        .zip(input.par_chunks(CHUNK_SIZE))
+        .try_for_each(|(o, i)| slice::scale(coefficient, i, o))
+}
+
+/// The parallel version of [`slice::mul_add`].
+///
+/// Returns [`GFError::DimensionMismatch`] if the slices have different lengths.
+pub fn mul_add(coefficient: GF, input: &[GF], output: &mut [GF]) -> Result<(), GFError> {
+    if input.len() != output.len() {
+        return Err(GFError::DimensionMismatch);
+    }
+
+    output
+        .par_chunks_mut(CHUNK_SIZE)
+        .zip(input.par_chunks(CHUNK_SIZE))
+        .try_for_each(|(o, i)| slice::mul_add(coefficient, i, o))
+}
+
+/// The parallel version of [`ReedSolomon::encode_shards`], which encodes every chunk of every
+/// parity shard in a separate task.
+///
+/// Returns [`GFError::DimensionMismatch`] if the number of shards is wrong, or if the shards
+/// don't all have the same length.
+pub fn encode_shards(code: &ReedSolomon, shards: &mut [Vec<u8>]) -> Result<(), GFError> {
+    if shards.len() != code.total_shards() {
+        return Err(GFError::DimensionMismatch);
+    }
+    let length = shards[0].len();
+    if shards.iter().any(|shard| shard.len() != length) {
+        return Err(GFError::DimensionMismatch);
+    }
+
+    let (data, parity) = shards.split_at_mut(code.data_shards());
+    parity
+        .par_iter_mut()
+        .enumerate()
+        .try_for_each(|(p, shard)| {
+            let row = code.generator_matrix().row(code.data_shards() + p);
+            slice::from_bytes_mut(shard)
+                .par_chunks_mut(CHUNK_SIZE)
+                .enumerate()
+                .try_for_each(|(c, chunk)| {
+                    chunk.iter_mut().for_each(|x| *x = GF(0));
+                    let start = c * CHUNK_SIZE;
+                    for (&coefficient, input) in row.iter().zip(data.iter()) {
+                        let input = slice::from_bytes(&input[start..start + chunk.len()]);
+                        slice::mul_add(coefficient, input, chunk)?;
+                    }
+                    Ok(())
+                })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn large_buffer(length: usize, seed: usize) -> Vec<GF> {
+        (0..length).map(|i| GF((i * 31 + seed) as u8)).collect()
+    }
+
+    #[test]
+    fn parallel_scale_matches_sequential() {
+        let input = large_buffer(5 * CHUNK_SIZE + 123, 7);
+        let mut sequential = vec![GF(0); input.len()];
+        let mut parallel = vec![GF(0); input.len()];
+
+        slice::scale(GF(0x53), &input, &mut sequential).unwrap();
+        scale(GF(0x53), &input, &mut parallel).unwrap();
+        assert_eq!(parallel, sequential);
+        assert_eq!(
+            scale(GF(2), &input[1..], &mut parallel),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn parallel_mul_add_matches_sequential() {
+        let input = large_buffer(5 * CHUNK_SIZE + 123, 7);
+        let mut sequential = large_buffer(input.len(), 1);
+        let mut parallel = sequential.clone();
+
+        slice::mul_add(GF(0xca), &input, &mut sequential).unwrap();
+        mul_add(GF(0xca), &input, &mut parallel).unwrap();
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn parallel_encode_matches_sequential() {
+        let code = ReedSolomon::new(6, 3).unwrap();
+        let length = 3 * CHUNK_SIZE + 5;
+        let mut sequential: Vec<Vec<u8>> = (0..9)
+            .map(|s| slice::as_bytes(&large_buffer(length, s)).to_vec())
+            .collect();
+        let mut parallel = sequential.clone();
+
+        code.encode_shards(&mut sequential).unwrap();
+        encode_shards(&code, &mut parallel).unwrap();
+        assert_eq!(parallel, sequential);
+
+        parallel[8].pop();
+        assert_eq!(
+            encode_shards(&code, &mut parallel),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+}
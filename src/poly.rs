@@ -0,0 +1,1126 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Polynomials with coefficients in GF(2<sup>8</sup>).
+
+use crate::{GFError, GFMatrix, GF};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Add, Mul, Sub};
+use core::str::FromStr;
+
+/// Polynomial with coefficients in the Galois field GF(2<sup>8</sup>).
+///
+/// The coefficients are stored from the lowest to the highest degree, so the coefficient at
+/// index `i` belongs to <i>x</i><sup>i</sup>. Trailing zero coefficients are never stored, which
+/// means that the zero polynomial has no coefficients at all.
+///
+/// The field arithmetic on the coefficients is isochronous, but the polynomial algorithms
+/// themselves branch on the degree of their operands.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, GFPoly};
+/// // x^2 + GF(3)*x + GF(2)
+/// let p = GFPoly::new(vec![GF(2), GF(3), GF(1)]);
+///
+/// assert_eq!(p.degree(), Some(2));
+/// assert_eq!(p.eval(GF(1)), GF(2) + GF(3) + GF(1));
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Hash, Default)]
+pub struct GFPoly {
+    coefficients: Vec<GF>,
+}
+
+impl GFPoly {
+    /// Creates a polynomial from its coefficients, ordered from the lowest to the highest degree.
+    pub fn new(mut coefficients: Vec<GF>) -> Self {
+        while coefficients.last() == Some(&GF(0)) {
+            coefficients.pop();
+        }
+        GFPoly { coefficients }
+    }
+
+    /// The zero polynomial.
+    pub fn zero() -> Self {
+        GFPoly::new(Vec::new())
+    }
+
+    /// The polynomial `coefficient * x^degree`.
+    pub fn monomial(coefficient: GF, degree: usize) -> Self {
+        let mut coefficients = vec![GF(0); degree + 1];
+        coefficients[degree] = coefficient;
+        GFPoly::new(coefficients)
+    }
+
+    /// The coefficients, ordered from the lowest to the highest degree.
+    pub fn coefficients(&self) -> &[GF] {
+        &self.coefficients
+    }
+
+    /// The degree of the polynomial, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// Returns `true` if this is the zero polynomial.
+    pub fn is_zero(&self) -> bool {
+        self.coefficients.is_empty()
+    }
+
+    /// The coefficient of the highest degree term, or `GF(0)` for the zero polynomial.
+    pub fn leading_coefficient(&self) -> GF {
+        self.coefficients.last().copied().unwrap_or(GF(0))
+    }
+
+    /// Evaluates the polynomial at `x` with Horner's method.
+    pub fn eval(&self, x: GF) -> GF {
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(GF(0), |acc, &c| acc * x + c)
+    }
+
+    /// Evaluates the polynomial at the square matrix `m` with Horner's method, where the constant
+    /// term is multiplied by the identity matrix.
+    ///
+    /// # Panics
+    /// Panics if the matrix is not square.
+    pub fn eval_matrix(&self, m: &GFMatrix) -> GFMatrix {
+        assert_eq!(m.rows(), m.cols(), "the matrix must be square");
+
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(GFMatrix::zero(m.rows(), m.cols()), |acc, &c| {
+                let mut next = &acc * m;
+                for i in 0..m.rows() {
+                    next[(i, i)] += c;
+                }
+                next
+            })
+    }
+
+    /// Evaluates the polynomial at every point in `points`.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// let p = GFPoly::new(vec![GF(1), GF(1)]);
+    /// assert_eq!(p.eval_many(&[GF(0), GF(1), GF(2)]), vec![GF(1), GF(0), GF(3)]);
+    /// ```
+    pub fn eval_many(&self, points: &[GF]) -> Vec<GF> {
+        points.iter().map(|&x| self.eval(x)).collect()
+    }
+
+    /// Finds the roots among the nonzero elements with a Chien search, which evaluates the
+    /// polynomial at &alpha;<sup>0</sup>, &alpha;<sup>1</sup>, ..., &alpha;<sup>254</sup> for
+    /// &alpha; = [`GF::GENERATOR`], and returns the roots in that order. Zero is never returned,
+    /// and the zero polynomial has every nonzero element as a root.
+    ///
+    /// Instead of a full evaluation at every point, every term <i>c</i><sub>k</sub>
+    /// <i>x</i><sup>k</sup> is kept in a register and multiplied by &alpha;<sup>k</sup> to step
+    /// to the next point, so every point costs one multiplication per coefficient. This is how
+    /// hardware decoders locate the errors with the error-locator polynomial of a Reed-Solomon
+    /// or BCH code.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// // (x + 2)(x + 3)
+    /// let p = GFPoly::new(vec![GF(6), GF(1), GF(1)]);
+    ///
+    /// assert_eq!(p.chien_search(), vec![GF(3), GF(2)]);
+    /// ```
+    pub fn chien_search(&self) -> Vec<GF> {
+        let mut terms = self.coefficients.clone();
+        let steps: Vec<GF> = (0..terms.len())
+            .map(|k| GF::GENERATOR.pow(k as u32))
+            .collect();
+
+        let mut roots = Vec::new();
+        let mut x = GF(1);
+        for _ in 0..255 {
+            if terms.iter().fold(GF(0), |sum, &t| sum + t) == GF(0) {
+                roots.push(x);
+            }
+            for (t, &step) in terms.iter_mut().zip(&steps) {
+                *t *= step;
+            }
+            x *= GF::GENERATOR;
+        }
+        roots
+    }
+
+    /// Scales the polynomial so that its leading coefficient is `GF(1)`.
+    /// The zero polynomial stays zero.
+    pub fn monic(&self) -> Self {
+        let inverse = self.leading_coefficient().multiplicative_inverse();
+        GFPoly::new(self.coefficients.iter().map(|&c| c * inverse).collect())
+    }
+
+    /// The formal derivative of the polynomial. In characteristic 2, the derivative of
+    /// <i>x</i><sup>i</sup> is <i>x</i><sup>i - 1</sup> for odd `i` and zero for even `i`,
+    /// since `i` is then a multiple of 2.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// // x^3 + 5x^2 + 7x + 2
+    /// let p = GFPoly::new(vec![GF(2), GF(7), GF(5), GF(1)]);
+    ///
+    /// // x^2 + 7
+    /// assert_eq!(p.derivative(), GFPoly::new(vec![GF(7), GF(0), GF(1)]));
+    /// ```
+    pub fn derivative(&self) -> Self {
+        GFPoly::new(
+            self.coefficients
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &c)| if i % 2 == 1 { c } else { GF(0) })
+                .collect(),
+        )
+    }
+
+    /// Polynomial long division. Returns the quotient and the remainder, such that
+    /// `self == quotient * divisor + remainder` and the degree of the remainder is lower than the
+    /// degree of the divisor.
+    ///
+    /// # Panics
+    /// Panics if `divisor` is the zero polynomial.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// let a = GFPoly::new(vec![GF(7), GF(0), GF(3), GF(1)]);
+    /// let b = GFPoly::new(vec![GF(2), GF(1)]);
+    /// let (q, r) = a.div_rem(&b);
+    ///
+    /// assert_eq!(&q * &b + r, a);
+    /// ```
+    pub fn div_rem(&self, divisor: &GFPoly) -> (GFPoly, GFPoly) {
+        let divisor_degree = divisor.degree().expect("division by the zero polynomial");
+        let inverse = divisor.leading_coefficient().multiplicative_inverse();
+
+        let mut remainder = self.coefficients.clone();
+        if remainder.len() <= divisor_degree {
+            return (GFPoly::zero(), self.clone());
+        }
+
+        let mut quotient = vec![GF(0); remainder.len() - divisor_degree];
+        for i in (0..quotient.len()).rev() {
+            let factor = remainder[i + divisor_degree] * inverse;
+            quotient[i] = factor;
+            for (r, &d) in remainder[i..].iter_mut().zip(&divisor.coefficients) {
+                *r -= factor * d;
+            }
+        }
+
+        remainder.truncate(divisor_degree);
+        (GFPoly::new(quotient), GFPoly::new(remainder))
+    }
+
+    /// Divides by <i>x</i><sup>n</sup>, which splits the polynomial at degree `n`. The quotient
+    /// consists of the terms of degree `n` and higher, shifted down by `n`, and the remainder
+    /// consists of the terms with a degree lower than `n`. No field operations are needed.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// let a = GFPoly::new(vec![GF(1), GF(2), GF(3), GF(4)]);
+    /// let (q, r) = a.divmod_xn(1);
+    ///
+    /// assert_eq!(q, GFPoly::new(vec![GF(2), GF(3), GF(4)]));
+    /// assert_eq!(r, GFPoly::new(vec![GF(1)]));
+    /// ```
+    pub fn divmod_xn(&self, n: usize) -> (GFPoly, GFPoly) {
+        let split = usize::min(n, self.coefficients.len());
+        let (low, high) = self.coefficients.split_at(split);
+        (GFPoly::new(high.to_vec()), GFPoly::new(low.to_vec()))
+    }
+
+    /// The monic greatest common divisor of two polynomials, computed with the Euclidean
+    /// algorithm. The greatest common divisor of two zero polynomials is the zero polynomial.
+    pub fn gcd(&self, other: &GFPoly) -> GFPoly {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let r = a.div_rem(&b).1;
+            a = b;
+            b = r;
+        }
+        a.monic()
+    }
+
+    /// The minimal polynomial of `a` over GF(2), which is the monic polynomial of the lowest
+    /// degree with coefficients in GF(2) that has `a` as a root. It is the product of
+    /// (<i>x</i> - <i>c</i>) over the [`conjugates`](GF::conjugates) <i>c</i> of `a`, so its
+    /// degree divides 8.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// // The minimal polynomial of x is the AES polynomial x^8 + x^4 + x^3 + x + 1.
+    /// let p = GFPoly::minimal_polynomial(GF(2));
+    /// let aes: Vec<GF> = (0..9).map(|i| GF((0x11b >> i) as u8 & 1)).collect();
+    ///
+    /// assert_eq!(p, GFPoly::new(aes));
+    /// ```
+    pub fn minimal_polynomial(a: GF) -> GFPoly {
+        a.conjugates()
+            .into_iter()
+            .fold(GFPoly::monomial(GF(1), 0), |acc, c| {
+                &acc * &GFPoly::new(vec![c, GF(1)])
+            })
+    }
+
+    /// The resultant of two polynomials, which is zero exactly when they have a common factor.
+    /// For monic polynomials that split into linear factors, it is the product of the
+    /// differences of their roots.
+    ///
+    /// It is calculated with the Euclidean algorithm: with <i>r</i> = <i>a</i> mod <i>b</i>,
+    /// res(<i>a</i>, <i>b</i>) = lc(<i>b</i>)<sup>deg <i>a</i> - deg <i>r</i></sup>
+    /// res(<i>b</i>, <i>r</i>), as the sign is always positive in characteristic 2. The resultant
+    /// with the zero polynomial is zero.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// let x_plus_3 = GFPoly::new(vec![GF(3), GF(1)]);
+    /// let x_plus_7 = GFPoly::new(vec![GF(7), GF(1)]);
+    ///
+    /// assert_eq!(x_plus_3.resultant(&x_plus_7), GF(3) - GF(7));
+    /// assert_eq!(x_plus_3.resultant(&(&x_plus_3 * &x_plus_7)), GF(0));
+    /// ```
+    pub fn resultant(&self, other: &GFPoly) -> GF {
+        let mut result = GF(1);
+        let mut a = self.clone();
+        let mut b = other.clone();
+
+        while let (Some(m), Some(n)) = (a.degree(), b.degree()) {
+            if n == 0 {
+                return result * b.leading_coefficient().pow(m as u32);
+            }
+            let r = a.div_rem(&b).1;
+            match r.degree() {
+                None => return GF(0),
+                Some(k) => result *= b.leading_coefficient().pow((m - k) as u32),
+            }
+            a = core::mem::replace(&mut b, r);
+        }
+        GF(0)
+    }
+
+    /// The extended Euclidean algorithm. Returns the monic greatest common divisor `g` of both
+    /// polynomials, together with the polynomials `s` and `t` such that
+    /// `s * self + t * other == g`.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// let a = GFPoly::new(vec![GF(3), GF(0), GF(1)]);
+    /// let b = GFPoly::new(vec![GF(5), GF(1)]);
+    /// let (g, s, t) = a.extended_gcd(&b);
+    ///
+    /// assert_eq!(g, a.gcd(&b));
+    /// assert_eq!(&s * &a + &t * &b, g);
+    /// ```
+    pub fn extended_gcd(&self, other: &GFPoly) -> (GFPoly, GFPoly, GFPoly) {
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut s0, mut s1) = (GFPoly::monomial(GF(1), 0), GFPoly::zero());
+        let (mut t0, mut t1) = (GFPoly::zero(), GFPoly::monomial(GF(1), 0));
+
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(&r1);
+            let s = &s0 - &(&q * &s1);
+            let t = &t0 - &(&q * &t1);
+            r0 = core::mem::replace(&mut r1, r);
+            s0 = core::mem::replace(&mut s1, s);
+            t0 = core::mem::replace(&mut t1, t);
+        }
+
+        let scale = GFPoly::monomial(r0.leading_coefficient().multiplicative_inverse(), 0);
+        (r0.monic(), &s0 * &scale, &t0 * &scale)
+    }
+
+    /// Returns `true` if the polynomial has no repeated factors, which means that
+    /// `gcd(self, self.derivative())` is a nonzero constant. Nonzero constant polynomials are
+    /// squarefree, the zero polynomial is not.
+    ///
+    /// In characteristic 2, the derivative of a square such as <i>x</i><sup>2</sup> + 1 is zero, so
+    /// then the greatest common divisor is the polynomial itself.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// let x_plus_3 = GFPoly::new(vec![GF(3), GF(1)]);
+    /// let x_plus_7 = GFPoly::new(vec![GF(7), GF(1)]);
+    ///
+    /// assert!((&x_plus_3 * &x_plus_7).is_squarefree());
+    /// assert!(!(&x_plus_3 * &x_plus_3).is_squarefree());
+    /// ```
+    pub fn is_squarefree(&self) -> bool {
+        self.gcd(&self.derivative()).degree() == Some(0)
+    }
+
+    /// Calculates the unique polynomial of the lowest degree that passes through all given
+    /// `(x, y)` points, using Newton's form of the interpolation polynomial.
+    ///
+    /// Returns [`GFError::DuplicatePoint`] if two points share the same `x` coordinate.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// let points = [(GF(1), GF(7)), (GF(2), GF(12)), (GF(3), GF(200))];
+    /// let p = GFPoly::interpolate(&points).unwrap();
+    ///
+    /// for &(x, y) in &points {
+    ///     assert_eq!(p.eval(x), y);
+    /// }
+    /// ```
+    pub fn interpolate(points: &[(GF, GF)]) -> Result<GFPoly, GFError> {
+        let mut result = GFPoly::zero();
+        // The product of (x - x_j) over all points that have been processed so far.
+        let mut basis = GFPoly::monomial(GF(1), 0);
+
+        for &(x, y) in points {
+            let denominator = basis.eval(x);
+            if denominator == GF(0) {
+                return Err(GFError::DuplicatePoint);
+            }
+
+            let c = (y - result.eval(x)) * denominator.multiplicative_inverse();
+            result = &result + &(&basis * &GFPoly::monomial(c, 0));
+            basis = &basis * &GFPoly::new(vec![x, GF(1)]);
+        }
+
+        Ok(result)
+    }
+
+    /// Splits a squarefree polynomial into factors whose irreducible components all have the
+    /// same degree. Every returned pair `(d, factor)` holds a monic `factor` that is the product
+    /// of all irreducible factors of degree `d`.
+    ///
+    /// The factors are found by taking the greatest common divisor with
+    /// <i>x</i><sup>q<sup>d</sup></sup> - <i>x</i> for increasing `d`, where q = 2<sup>8</sup>.
+    /// The product of the returned factors is `self.monic()`. Constant polynomials have no
+    /// factors.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// // (x + GF(1)) * (x + GF(2))
+    /// let p = GFPoly::new(vec![GF(2), GF(3), GF(1)]);
+    ///
+    /// assert_eq!(p.distinct_degree_factorization(), vec![(1, p)]);
+    /// ```
+    pub fn distinct_degree_factorization(&self) -> Vec<(usize, GFPoly)> {
+        let x = GFPoly::monomial(GF(1), 1);
+        let mut factors = Vec::new();
+        let mut remaining = self.monic();
+        // The invariant is that h == x^(q^(d - 1)) mod remaining
+        let mut h = x.clone();
+        let mut d = 1;
+
+        while remaining.degree().is_some_and(|n| n >= 2 * d) {
+            // Raising to the power q = 2^8 is the same as squaring eight times.
+            for _ in 0..8 {
+                h = (&h * &h).div_rem(&remaining).1;
+            }
+
+            let factor = remaining.gcd(&(&h - &x));
+            if factor.degree() != Some(0) {
+                remaining = remaining.div_rem(&factor).0;
+                h = h.div_rem(&remaining).1;
+                factors.push((d, factor));
+            }
+            d += 1;
+        }
+
+        // Whatever is left over can only be a single irreducible factor.
+        if let Some(n) = remaining.degree() {
+            if n > 0 {
+                factors.push((n, remaining));
+            }
+        }
+
+        factors
+    }
+
+    /// Multiplies two polynomials with the Toom-Cook-3 algorithm, which is faster than the
+    /// schoolbook multiplication of the `*` operator for large polynomials.
+    ///
+    /// Both operands are split into three parts, which are the coefficients of polynomials in
+    /// <i>y</i> = <i>x</i><sup>m</sup>. Their product then has five coefficients, which follow
+    /// from five recursive products: the products at <i>y</i> = 0, 1, 2 and 3, and the product
+    /// of the leading parts. Interpolating those costs only multiplications by constants, so
+    /// the running time is <i>O</i>(<i>n</i><sup>1.47</sup>) instead of
+    /// <i>O</i>(<i>n</i><sup>2</sup>). Small products fall back to schoolbook multiplication.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, GFPoly};
+    /// let a = GFPoly::new((0..100).map(|i| GF(i as u8)).collect());
+    /// let b = GFPoly::new((0..80).map(|i| GF(3 * i as u8)).collect());
+    ///
+    /// assert_eq!(a.mul_toom3(&b), &a * &b);
+    /// ```
+    pub fn mul_toom3(&self, rhs: &GFPoly) -> GFPoly {
+        if self.is_zero() || rhs.is_zero() {
+            return GFPoly::zero();
+        }
+
+        // The coefficients c1, c2 and c3 satisfy V (c1, c2, c3) = s for the evaluations s at the
+        // points 1, 2 and 3, where V is a Vandermonde matrix without the column of ones.
+        let vandermonde = GFMatrix::from_fn(3, 3, |i, j| TOOM3_POINTS[i].pow(j as u32 + 1));
+        let interpolation = vandermonde
+            .inverse()
+            .expect("the evaluation points are distinct and nonzero");
+        GFPoly::new(toom3(&self.coefficients, &rhs.coefficients, &interpolation))
+    }
+}
+
+/// The nonzero finite evaluation points of Toom-Cook-3, next to 0 and infinity.
+const TOOM3_POINTS: [GF; 3] = [GF(1), GF(2), GF(3)];
+
+/// Products with an operand shorter than this use schoolbook multiplication.
+const TOOM3_THRESHOLD: usize = 128;
+
+/// The coefficients of the product of `a` and `b`, which may have trailing zeros.
+fn toom3(a: &[GF], b: &[GF], interpolation: &GFMatrix) -> Vec<GF> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) < TOOM3_THRESHOLD {
+        let mut product = vec![GF(0); a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                product[i + j] += x * y;
+            }
+        }
+        return product;
+    }
+
+    let m = a.len().max(b.len()).div_ceil(3);
+    let split = |x: &[GF]| -> [Vec<GF>; 3] {
+        core::array::from_fn(|i| x[(i * m).min(x.len())..((i + 1) * m).min(x.len())].to_vec())
+    };
+    let (a, b) = (split(a), split(b));
+    let evaluate = |parts: &[Vec<GF>; 3], point: GF| {
+        let mut sum = vec![GF(0); m];
+        add_scaled(&mut sum, &parts[0], GF(1), 0);
+        add_scaled(&mut sum, &parts[1], point, 0);
+        add_scaled(&mut sum, &parts[2], point * point, 0);
+        sum
+    };
+
+    let c0 = toom3(&a[0], &b[0], interpolation);
+    let c4 = toom3(&a[2], &b[2], interpolation);
+    // The evaluations at the points, without the contributions of c0 and c4.
+    let s: Vec<Vec<GF>> = TOOM3_POINTS
+        .iter()
+        .map(|&point| {
+            let mut sum = toom3(&evaluate(&a, point), &evaluate(&b, point), interpolation);
+            add_scaled(&mut sum, &c0, GF(1), 0);
+            add_scaled(&mut sum, &c4, point.pow(4), 0);
+            sum
+        })
+        .collect();
+
+    let mut product = Vec::new();
+    add_scaled(&mut product, &c0, GF(1), 0);
+    add_scaled(&mut product, &c4, GF(1), 4 * m);
+    for j in 0..3 {
+        for (i, s) in s.iter().enumerate() {
+            add_scaled(&mut product, s, interpolation[(j, i)], (j + 1) * m);
+        }
+    }
+    product
+}
+
+/// Calculates `output[offset + i] += coefficient * input[i]`, and extends `output` with zeros
+/// if it is too short.
+fn add_scaled(output: &mut Vec<GF>, input: &[GF], coefficient: GF, offset: usize) {
+    if output.len() < offset + input.len() {
+        output.resize(offset + input.len(), GF(0));
+    }
+    let output = output[offset..].iter_mut().zip(input);
+    if coefficient == GF(1) {
+        output.for_each(|(o, &i)| *o += i);
+    } else {
+        output.for_each(|(o, &i)| *o += coefficient * i);
+    }
+}
+
+/// Evaluates a polynomial at a fixed point, while its coefficients arrive one at a time from the
+/// highest to the lowest degree.
+///
+/// This is the online version of [`GFPoly::eval`], which is useful when the coefficients are
+/// generated lazily.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, GFPoly, HornerEval};
+/// let mut horner = HornerEval::new(GF(2));
+/// horner.push_coeff(GF(1));
+/// horner.push_coeff(GF(3));
+/// horner.push_coeff(GF(2));
+///
+/// let p = GFPoly::new(vec![GF(2), GF(3), GF(1)]);
+/// assert_eq!(horner.value(), p.eval(GF(2)));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct HornerEval {
+    x: GF,
+    acc: GF,
+}
+
+impl HornerEval {
+    /// Starts the evaluation of a polynomial at the point `x`.
+    pub fn new(x: GF) -> Self {
+        HornerEval { x, acc: GF(0) }
+    }
+
+    /// Adds the next coefficient, which belongs to a degree that is one lower than the previous
+    /// coefficient.
+    pub fn push_coeff(&mut self, c: GF) {
+        self.acc = self.acc * self.x + c;
+    }
+
+    /// The value of the polynomial made up of all coefficients that have been pushed so far.
+    pub fn value(&self) -> GF {
+        self.acc
+    }
+}
+
+impl From<Vec<GF>> for GFPoly {
+    fn from(coefficients: Vec<GF>) -> Self {
+        GFPoly::new(coefficients)
+    }
+}
+
+/// Formats the polynomial from the highest to the lowest degree, like
+/// `x^2 + GF(3)*x + GF(1)`. Terms with a zero coefficient are left out, and coefficients of
+/// `GF(1)` are only written for the constant term. The zero polynomial is formatted as `0`.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, GFPoly};
+/// let p = GFPoly::new(vec![GF(1), GF(3), GF(1), GF(0), GF(200)]);
+///
+/// assert_eq!(p.to_string(), "GF(200)*x^4 + x^2 + GF(3)*x + GF(1)");
+/// assert_eq!(p.to_string().parse(), Ok(p));
+/// ```
+impl fmt::Display for GFPoly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.write_str("0");
+        }
+
+        let terms = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|&(_, &c)| c != GF(0));
+        for (i, (degree, &c)) in terms.enumerate() {
+            if i > 0 {
+                f.write_str(" + ")?;
+            }
+            match (degree, c) {
+                (0, c) => write!(f, "GF({})", c.0)?,
+                (1, GF(1)) => f.write_str("x")?,
+                (1, c) => write!(f, "GF({})*x", c.0)?,
+                (degree, GF(1)) => write!(f, "x^{}", degree)?,
+                (degree, c) => write!(f, "GF({})*x^{}", c.0, degree)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The highest degree that [`GFPoly::from_str`] accepts. A short string like `x^N` can ask for
+/// an arbitrarily large polynomial, so the degree is bounded to keep untrusted input from
+/// exhausting the memory.
+const MAX_PARSED_DEGREE: usize = 1 << 20;
+
+/// Parses the format that is produced by [`Display`](fmt::Display). Whitespace around the
+/// terms is ignored, and terms of the same degree are added together.
+///
+/// Returns [`GFError::InvalidFormat`] if the string is not a polynomial in that format, or if a
+/// term has a degree above 2<sup>20</sup>.
+impl FromStr for GFPoly {
+    type Err = GFError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "0" {
+            return Ok(GFPoly::zero());
+        }
+
+        let mut coefficients = Vec::new();
+        for term in s.split('+') {
+            let (c, degree) = parse_term(term.trim())?;
+            let length = degree
+                .checked_add(1)
+                .filter(|_| degree <= MAX_PARSED_DEGREE)
+                .ok_or(GFError::InvalidFormat)?;
+            if coefficients.len() < length {
+                coefficients.resize(length, GF(0));
+            }
+            coefficients[degree] += c;
+        }
+        Ok(GFPoly::new(coefficients))
+    }
+}
+
+/// Parses a single term like `GF(3)*x^2`, `x^2`, `x`, or `GF(3)`.
+fn parse_term(term: &str) -> Result<(GF, usize), GFError> {
+    let (coefficient, power) = match term.strip_prefix("GF(") {
+        Some(rest) => {
+            let end = rest.find(')').ok_or(GFError::InvalidFormat)?;
+            let coefficient = rest[..end]
+                .trim()
+                .parse()
+                .map_err(|_| GFError::InvalidFormat)?;
+            let rest = rest[end + 1..].trim_start();
+            if rest.is_empty() {
+                return Ok((GF(coefficient), 0));
+            }
+            let power = rest.strip_prefix('*').ok_or(GFError::InvalidFormat)?;
+            (GF(coefficient), power.trim_start())
+        }
+        None => (GF(1), term),
+    };
+
+    let exponent = power.strip_prefix('x').ok_or(GFError::InvalidFormat)?;
+    let degree = if exponent.is_empty() {
+        1
+    } else {
+        exponent
+            .strip_prefix('^')
+            .ok_or(GFError::InvalidFormat)?
+            .parse()
+            .map_err(|_| GFError::InvalidFormat)?
+    };
+    Ok((coefficient, degree))
+}
+
+/// Adding two polynomials adds the coefficients of equal degree.
+impl Add<&GFPoly> for &GFPoly {
+    type Output = GFPoly;
+
+    fn add(self, rhs: &GFPoly) -> Self::Output {
+        let (long, short) = if self.coefficients.len() >= rhs.coefficients.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+
+        let mut coefficients = long.coefficients.clone();
+        for (c, &s) in coefficients.iter_mut().zip(&short.coefficients) {
+            *c += s;
+        }
+        GFPoly::new(coefficients)
+    }
+}
+
+impl Add for GFPoly {
+    type Output = GFPoly;
+
+    fn add(self, rhs: GFPoly) -> Self::Output {
+        &self + &rhs
+    }
+}
+
+/// Subtracting two polynomials is the same as adding them, since every element of
+/// GF(2<sup>8</sup>) is its own additive inverse.
+impl Sub<&GFPoly> for &GFPoly {
+    type Output = GFPoly;
+
+    fn sub(self, rhs: &GFPoly) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl Sub for GFPoly {
+    type Output = GFPoly;
+
+    fn sub(self, rhs: GFPoly) -> Self::Output {
+        &self - &rhs
+    }
+}
+
+impl Mul<&GFPoly> for &GFPoly {
+    type Output = GFPoly;
+
+    fn mul(self, rhs: &GFPoly) -> Self::Output {
+        if self.is_zero() || rhs.is_zero() {
+            return GFPoly::zero();
+        }
+
+        let mut coefficients = vec![GF(0); self.coefficients.len() + rhs.coefficients.len() - 1];
+        for (i, &a) in self.coefficients.iter().enumerate() {
+            for (j, &b) in rhs.coefficients.iter().enumerate() {
+                coefficients[i + j] += a * b;
+            }
+        }
+        GFPoly::new(coefficients)
+    }
+}
+
+impl Mul for GFPoly {
+    type Output = GFPoly;
+
+    fn mul(self, rhs: GFPoly) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn poly(coefficients: &[u8]) -> GFPoly {
+        GFPoly::new(coefficients.iter().map(|&c| GF(c)).collect())
+    }
+
+    #[test]
+    fn trailing_zeros_are_removed() {
+        assert_eq!(poly(&[1, 2, 0, 0]), poly(&[1, 2]));
+        assert_eq!(poly(&[0, 0]), GFPoly::zero());
+        assert_eq!(GFPoly::zero().degree(), None);
+    }
+
+    #[test]
+    fn horner_eval_matches_batch_evaluation() {
+        let p = poly(&[0x53, 0x01, 0xca, 0x00, 0x13, 0x57]);
+        for x in [0x00, 0x01, 0x02, 0x8d, 0xff] {
+            let mut horner = HornerEval::new(GF(x));
+            for &c in p.coefficients().iter().rev() {
+                horner.push_coeff(c);
+            }
+            assert_eq!(horner.value(), p.eval(GF(x)));
+        }
+        assert_eq!(HornerEval::new(GF(7)).value(), GF(0));
+    }
+
+    #[test]
+    fn divmod_xn_reconstructs_polynomial() {
+        let a = poly(&[0x53, 0x00, 0xca, 0x00, 0x13, 0x57]);
+        let (q, r) = a.divmod_xn(3);
+
+        assert_eq!(q, poly(&[0x00, 0x13, 0x57]));
+        assert_eq!(r, poly(&[0x53, 0x00, 0xca]));
+        assert_eq!(&q * &GFPoly::monomial(GF(1), 3) + r.clone(), a);
+        assert_eq!((q, r), a.div_rem(&GFPoly::monomial(GF(1), 3)));
+
+        assert_eq!(a.divmod_xn(0), (a.clone(), GFPoly::zero()));
+        assert_eq!(a.divmod_xn(10), (GFPoly::zero(), a));
+    }
+
+    #[test]
+    fn derivative_drops_even_terms() {
+        let p = poly(&[0x53, 0x01, 0xca, 0x00, 0x13, 0x57]);
+        assert_eq!(p.derivative(), poly(&[0x01, 0x00, 0x00, 0x00, 0x57]));
+        assert_eq!(poly(&[0x53]).derivative(), GFPoly::zero());
+
+        // The product rule also holds in characteristic 2.
+        let q = poly(&[0x02, 0x80, 0x03, 0x11]);
+        let product_rule = &p.derivative() * &q + &p * &q.derivative();
+        assert_eq!((&p * &q).derivative(), product_rule);
+    }
+
+    #[test]
+    fn chien_search_matches_brute_force() {
+        let polynomials = [
+            GFPoly::new(vec![GF(6), GF(1), GF(1)]),
+            GFPoly::new(vec![GF(0), GF(0x53), GF(0x07), GF(0xca)]),
+            GFPoly::new(vec![GF(1), GF(0), GF(0), GF(0), GF(0), GF(1)]),
+            GFPoly::minimal_polynomial(GF(0x53)),
+            GFPoly::new(vec![GF(0x12)]),
+            GFPoly::zero(),
+        ];
+        for p in &polynomials {
+            let expected: Vec<GF> = (0..255)
+                .map(|i| GF::GENERATOR.pow(i))
+                .filter(|&x| p.eval(x) == GF(0))
+                .collect();
+            assert_eq!(p.chien_search(), expected);
+        }
+        assert_eq!(GFPoly::minimal_polynomial(GF(0x53)).chien_search().len(), 8);
+        assert_eq!(GFPoly::zero().chien_search().len(), 255);
+    }
+
+    #[test]
+    fn chien_search_finds_error_locations() {
+        // The locator (1 - X_1 x)(1 - X_2 x) has the inverses of the locators as roots.
+        let locators = [GF::GENERATOR.pow(3), GF::GENERATOR.pow(200)];
+        let locator = locators.iter().fold(GFPoly::new(vec![GF(1)]), |p, &x| {
+            &p * &GFPoly::new(vec![GF(1), x])
+        });
+
+        let mut roots = locator.chien_search();
+        roots.sort();
+        let mut expected: Vec<GF> = locators
+            .iter()
+            .map(|x| x.multiplicative_inverse())
+            .collect();
+        expected.sort();
+        assert_eq!(roots, expected);
+    }
+
+    #[test]
+    fn squarefree_polynomials() {
+        let x_minus_3 = poly(&[3, 1]);
+        let x_minus_7 = poly(&[7, 1]);
+        assert!(!(&x_minus_3 * &x_minus_3).is_squarefree());
+        assert!((&x_minus_3 * &x_minus_7).is_squarefree());
+        assert!(!(&(&x_minus_3 * &x_minus_3) * &x_minus_7).is_squarefree());
+
+        // The derivative of x^2 + 1 = (x + 1)^2 is zero.
+        assert!(!poly(&[1, 0, 1]).is_squarefree());
+        assert!(poly(&[0x53]).is_squarefree());
+        assert!(!GFPoly::zero().is_squarefree());
+    }
+
+    #[test]
+    fn div_rem_reconstructs_dividend() {
+        let a = poly(&[0x53, 0x01, 0xca, 0x00, 0x13, 0x57]);
+        let b = poly(&[0x02, 0x80, 0x03]);
+        let (q, r) = a.div_rem(&b);
+
+        assert_eq!(q.degree(), Some(3));
+        assert!(r.degree() < b.degree());
+        assert_eq!(&q * &b + r, a);
+    }
+
+    #[test]
+    fn gcd_of_polynomials_with_common_factor() {
+        let common = poly(&[0x07, 0x01]);
+        let a = &common * &poly(&[0x02, 0x01]);
+        let b = &common * &poly(&[0x03, 0x05, 0x01]);
+
+        assert_eq!(a.gcd(&b), common);
+        assert_eq!(a.gcd(&GFPoly::zero()), a);
+    }
+
+    #[test]
+    fn extended_gcd_bezout_identity() {
+        let common = poly(&[0x1b, 0x01]);
+        let a = &common * &poly(&[0x02, 0x00, 0x08]);
+        let b = &common * &poly(&[0x35, 0x11, 0x04, 0x01]);
+        let (g, s, t) = a.extended_gcd(&b);
+
+        assert_eq!(g, common);
+        assert_eq!(&s * &a + &t * &b, g);
+    }
+
+    #[test]
+    fn interpolate_through_points() {
+        let p = poly(&[0x12, 0x34, 0x56, 0x78]);
+        let points: Vec<_> = [1, 2, 3, 4, 5, 6]
+            .iter()
+            .map(|&x| (GF(x), p.eval(GF(x))))
+            .collect();
+
+        assert_eq!(GFPoly::interpolate(&points), Ok(p));
+        assert_eq!(GFPoly::interpolate(&[]), Ok(GFPoly::zero()));
+    }
+
+    #[test]
+    fn interpolate_duplicate_points() {
+        let points = [(GF(1), GF(2)), (GF(3), GF(4)), (GF(1), GF(5))];
+        assert_eq!(GFPoly::interpolate(&points), Err(GFError::DuplicatePoint));
+    }
+
+    #[test]
+    fn distinct_degree_factorization_groups_by_degree() {
+        let linear = &poly(&[0x01, 0x01]) * &poly(&[0x02, 0x01]);
+        // x^2 + x + c is irreducible over GF(2^8) exactly when c has trace 1.
+        let quadratic = poly(&[0x20, 0x01, 0x01]);
+        // x^3 + x + 1 is irreducible over GF(2), and stays irreducible over GF(2^8) because
+        // 3 and 8 are coprime.
+        let cubic = poly(&[0x01, 0x01, 0x00, 0x01]);
+        let p = &(&linear * &quadratic) * &cubic;
+
+        let factors = p.distinct_degree_factorization();
+        assert_eq!(factors, vec![(1, linear), (2, quadratic), (3, cubic)]);
+
+        let product = factors
+            .into_iter()
+            .fold(poly(&[0x01]), |acc, (_, factor)| acc * factor);
+        assert_eq!(product, p);
+    }
+
+    #[test]
+    fn distinct_degree_factorization_of_non_monic_polynomial() {
+        let p = poly(&[0x20, 0x01, 0x01]);
+        let scaled = &p * &poly(&[0x57]);
+
+        assert_eq!(scaled.distinct_degree_factorization(), vec![(2, p)]);
+        assert!(poly(&[0x57]).distinct_degree_factorization().is_empty());
+    }
+
+    #[test]
+    fn display_and_parse_round_trip() {
+        let polynomials = [
+            GFPoly::zero(),
+            poly(&[0x01]),
+            poly(&[0xff]),
+            poly(&[0x00, 0x01]),
+            poly(&[0x03, 0x00, 0x01]),
+            poly(&[0x01, 0x03, 0x01]),
+            poly(&[0x00, 0x00, 0x00, 0x09, 0x80]),
+        ];
+
+        for p in polynomials {
+            assert_eq!(p.to_string().parse::<GFPoly>(), Ok(p));
+        }
+    }
+
+    #[test]
+    fn display_format() {
+        assert_eq!(GFPoly::zero().to_string(), "0");
+        assert_eq!(poly(&[0x01]).to_string(), "GF(1)");
+        assert_eq!(poly(&[0x00, 0x01]).to_string(), "x");
+        assert_eq!(
+            poly(&[0x01, 0x03, 0x01]).to_string(),
+            "x^2 + GF(3)*x + GF(1)"
+        );
+        assert_eq!(
+            poly(&[0x00, 0x02, 0x00, 0x05]).to_string(),
+            "GF(5)*x^3 + GF(2)*x"
+        );
+    }
+
+    #[test]
+    fn parse_lenient_input() {
+        assert_eq!("  GF(2) * x^2+x ".parse(), Ok(poly(&[0x00, 0x01, 0x02])));
+        assert_eq!("x + x + GF(7)".parse(), Ok(poly(&[0x07])));
+    }
+
+    #[test]
+    fn parse_invalid_input() {
+        for s in [
+            "",
+            "y",
+            "GF(256)",
+            "GF(3",
+            "GF(3)x",
+            "x^",
+            "x^-1",
+            "x + + x",
+            "x^18446744073709551615",
+            "x^100000000000",
+        ] {
+            assert_eq!(s.parse::<GFPoly>(), Err(GFError::InvalidFormat), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn parse_degree_limit() {
+        assert_eq!(
+            "GF(3)*x^1048577 + x".parse::<GFPoly>(),
+            Err(GFError::InvalidFormat)
+        );
+        assert_eq!(
+            "x^1048576".parse(),
+            Ok(GFPoly::monomial(GF(1), MAX_PARSED_DEGREE))
+        );
+    }
+
+    #[test]
+    fn resultant_is_product_of_root_differences() {
+        let from_roots = |roots: &[u8]| {
+            roots.iter().fold(GFPoly::monomial(GF(1), 0), |acc, &r| {
+                &acc * &GFPoly::new(vec![GF(r), GF(1)])
+            })
+        };
+        let (roots_a, roots_b) = ([0x02, 0x53, 0x11], [0x07, 0xca, 0x80, 0x01]);
+        let (a, b) = (from_roots(&roots_a), from_roots(&roots_b));
+
+        let expected = roots_a.iter().fold(GF(1), |acc, &x| {
+            roots_b.iter().fold(acc, |acc, &y| acc * (GF(x) - GF(y)))
+        });
+        assert_ne!(expected, GF(0));
+        assert_eq!(a.resultant(&b), expected);
+        assert_eq!(b.resultant(&a), expected);
+
+        // Scaling a polynomial of degree 3 scales the resultant by the cube.
+        let scaled = &b * &GFPoly::monomial(GF(5), 0);
+        assert_eq!(a.resultant(&scaled), expected * GF(5).pow(3));
+    }
+
+    #[test]
+    fn resultant_of_polynomials_with_common_root() {
+        let common = poly(&[0x35, 0x01]);
+        let a = &common * &poly(&[0x01, 0x02, 0x03]);
+        let b = &common * &poly(&[0x09, 0x00, 0x00, 0x01]);
+        assert_eq!(a.resultant(&b), GF(0));
+
+        assert_eq!(a.resultant(&GFPoly::zero()), GF(0));
+        assert_eq!(poly(&[0x04]).resultant(&a), GF(0x04).pow(3));
+        assert_eq!(poly(&[0x04]).resultant(&poly(&[0x09])), GF(1));
+    }
+
+    #[test]
+    fn minimal_polynomials_are_binary() {
+        let mut total_degree = 0;
+        for a in 0u8..=255u8 {
+            let p = GFPoly::minimal_polynomial(GF(a));
+            let degree = p.degree().unwrap();
+            assert!(p.coefficients().iter().all(|&c| c == GF(0) || c == GF(1)));
+            assert_eq!(p.eval(GF(a)), GF(0));
+            assert_eq!(8 % degree, 0);
+            total_degree += degree;
+        }
+        // The elements of the subfields GF(2), GF(4) and GF(16) have minimal polynomials of
+        // degree 1, 2 and 4, and the remaining 240 elements have degree 8.
+        assert_eq!(total_degree, 2 + 2 * 2 + 12 * 4 + 240 * 8);
+    }
+
+    #[test]
+    fn toom3_matches_schoolbook() {
+        let pseudo_random = |len: usize, seed: usize| {
+            poly(
+                &(0..len)
+                    .map(|i| (i * 97 + seed * 31 + 1) as u8)
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        // Operands of 385 or more coefficients recurse twice.
+        for len_a in [1, 2, 127, 128, 129, 200, 385, 600] {
+            for len_b in [1, 128, 131, 390] {
+                let a = pseudo_random(len_a, len_b);
+                let b = pseudo_random(len_b, len_a + 1);
+                assert_eq!(a.mul_toom3(&b), &a * &b);
+            }
+        }
+        assert_eq!(GFPoly::zero().mul_toom3(&poly(&[1, 2])), GFPoly::zero());
+        assert_eq!(poly(&[1, 2]).mul_toom3(&GFPoly::zero()), GFPoly::zero());
+    }
+}
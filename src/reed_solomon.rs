@@ -0,0 +1,1565 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Reed-Solomon codes over GF(2<sup>8</sup>).
+
+use crate::{GFError, GFMatrix, GFPoly, GF};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A systematic Reed-Solomon code over GF(2<sup>8</sup>) with `data_shards` data symbols and
+/// `parity_shards` parity symbols per codeword.
+///
+/// The code is generated by the polynomial
+/// <i>g</i>(<i>x</i>) = (<i>x</i> - &alpha;<sup>0</sup>)(<i>x</i> - &alpha;<sup>1</sup>) &middot;&middot;&middot;
+/// (<i>x</i> - &alpha;<sup>m - 1</sup>), where &alpha; is [`GF::GENERATOR`] and <i>m</i> is the
+/// number of parity symbols. A codeword consists of the data symbols followed by the parity
+/// symbols. As a polynomial, its first symbol is the coefficient of the highest degree, and
+/// every codeword is a multiple of <i>g</i>(<i>x</i>).
+///
+/// For erasure coding, the code is applied to shards of bytes: byte `i` of all shards together
+/// forms a codeword. Any `data_shards` shards are enough to reconstruct all the others.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::ReedSolomon;
+/// let rs = ReedSolomon::new(3, 2).unwrap();
+/// let mut shards = vec![b"abcd".to_vec(), b"efgh".to_vec(), b"ijkl".to_vec(), vec![0; 4], vec![0; 4]];
+/// rs.encode_shards(&mut shards).unwrap();
+///
+/// let mut received: Vec<_> = shards.iter().cloned().map(Some).collect();
+/// received[0] = None;
+/// received[3] = None;
+/// rs.reconstruct_erasures(&mut received).unwrap();
+///
+/// assert_eq!(received[0].as_deref(), Some(&b"abcd"[..]));
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct ReedSolomon {
+    data_shards: usize,
+    parity_shards: usize,
+    /// The number of parity symbols that were dropped from the end of the codewords.
+    punctured: usize,
+    generator: GFPoly,
+    /// The systematic generator matrix, which maps the data symbols to the whole codeword. Its
+    /// first `data_shards` rows form the identity matrix.
+    matrix: GFMatrix,
+}
+
+impl ReedSolomon {
+    /// Creates a code with `data_shards` data symbols and `parity_shards` parity symbols per
+    /// codeword.
+    ///
+    /// Returns [`GFError::OutOfRange`] if there are no data shards, or if there are more than
+    /// 255 shards in total, since a codeword can't be longer than the multiplicative group of
+    /// the field.
+    pub fn new(data_shards: usize, parity_shards: usize) -> Result<Self, GFError> {
+        if data_shards == 0 || data_shards + parity_shards > 255 {
+            return Err(GFError::OutOfRange);
+        }
+
+        let generator = (0..parity_shards).fold(GFPoly::monomial(GF(1), 0), |acc, i| {
+            let root = GF::GENERATOR.pow(i as u32);
+            &acc * &GFPoly::new(vec![root, GF(1)])
+        });
+
+        // Column j of the generator matrix is the codeword of the j-th unit vector. Its parity
+        // symbols are the remainder of x^(n - 1 - j) divided by the generator polynomial.
+        let n = data_shards + parity_shards;
+        let mut matrix = GFMatrix::zero(n, data_shards);
+        for j in 0..data_shards {
+            matrix[(j, j)] = GF(1);
+            let remainder = GFPoly::monomial(GF(1), n - 1 - j).div_rem(&generator).1;
+            for (degree, &c) in remainder.coefficients().iter().enumerate() {
+                matrix[(n - 1 - degree, j)] = c;
+            }
+        }
+
+        Ok(ReedSolomon {
+            data_shards,
+            parity_shards,
+            punctured: 0,
+            generator,
+            matrix,
+        })
+    }
+
+    /// Shortens the code by removing `data_removed` data symbols from the start of every
+    /// codeword. This is equivalent to always setting those symbols to zero, so they don't have
+    /// to be sent. The number of parity symbols stays the same, and so does the number of errors
+    /// and erasures that can be corrected.
+    ///
+    /// Returns [`GFError::OutOfRange`] if not at least one data symbol would remain.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::ReedSolomon;
+    /// let shortened = ReedSolomon::new(10, 4).unwrap().with_shortened(7).unwrap();
+    ///
+    /// assert_eq!(shortened.data_shards(), 3);
+    /// assert_eq!(shortened.parity_shards(), 4);
+    /// ```
+    pub fn with_shortened(&self, data_removed: usize) -> Result<Self, GFError> {
+        if data_removed >= self.data_shards {
+            return Err(GFError::OutOfRange);
+        }
+
+        let s = data_removed;
+        let matrix = GFMatrix::from_fn(self.total_shards() - s, self.data_shards - s, |i, j| {
+            self.matrix[(i + s, j + s)]
+        });
+        Ok(ReedSolomon {
+            data_shards: self.data_shards - s,
+            matrix,
+            generator: self.generator.clone(),
+            ..*self
+        })
+    }
+
+    /// Punctures the code by removing `parity_removed` parity symbols from the end of every
+    /// codeword. Every removed parity symbol reduces the number of erasures that can be corrected
+    /// by one.
+    ///
+    /// Returns [`GFError::OutOfRange`] if there are fewer parity symbols than `parity_removed`.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::ReedSolomon;
+    /// let punctured = ReedSolomon::new(10, 4).unwrap().with_punctured(1).unwrap();
+    ///
+    /// assert_eq!(punctured.total_shards(), 13);
+    /// assert_eq!(punctured.punctured_shards(), 1);
+    /// ```
+    pub fn with_punctured(&self, parity_removed: usize) -> Result<Self, GFError> {
+        if parity_removed > self.parity_shards {
+            return Err(GFError::OutOfRange);
+        }
+
+        let rows: Vec<usize> = (0..self.total_shards() - parity_removed).collect();
+        Ok(ReedSolomon {
+            parity_shards: self.parity_shards - parity_removed,
+            punctured: self.punctured + parity_removed,
+            matrix: self.matrix.select_rows(&rows),
+            generator: self.generator.clone(),
+            ..*self
+        })
+    }
+
+    /// The number of data symbols per codeword.
+    pub fn data_shards(&self) -> usize {
+        self.data_shards
+    }
+
+    /// The number of parity symbols per codeword.
+    pub fn parity_shards(&self) -> usize {
+        self.parity_shards
+    }
+
+    /// The number of symbols per codeword.
+    pub fn total_shards(&self) -> usize {
+        self.data_shards + self.parity_shards
+    }
+
+    /// The number of parity symbols that were removed with [`ReedSolomon::with_punctured`].
+    pub fn punctured_shards(&self) -> usize {
+        self.punctured
+    }
+
+    /// The generator polynomial of the code. For a punctured code, this is the generator
+    /// polynomial before puncturing, and the codewords are only multiples of it when the
+    /// removed parity symbols are appended again.
+    pub fn generator_polynomial(&self) -> &GFPoly {
+        &self.generator
+    }
+
+    /// The systematic generator matrix of the code, with one row per codeword symbol and one
+    /// column per data symbol.
+    pub fn generator_matrix(&self) -> &GFMatrix {
+        &self.matrix
+    }
+
+    /// Encodes the data symbols into a codeword, which consists of the data followed by the
+    /// parity symbols.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the number of data symbols is wrong.
+    pub fn encode(&self, data: &[GF]) -> Result<Vec<GF>, GFError> {
+        if data.len() != self.data_shards {
+            return Err(GFError::DimensionMismatch);
+        }
+
+        let mut codeword = data.to_vec();
+        for p in self.data_shards..self.total_shards() {
+            let row = self.matrix.row(p);
+            codeword.push(
+                row.iter()
+                    .zip(data)
+                    .fold(GF(0), |acc, (&a, &d)| acc + a * d),
+            );
+        }
+        Ok(codeword)
+    }
+
+    /// Calculates the parity shards from the data shards. The shards are ordered as the data
+    /// shards followed by the parity shards, and the contents of the parity shards are
+    /// overwritten.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the number of shards is wrong, or if the shards
+    /// don't all have the same length.
+    pub fn encode_shards(&self, shards: &mut [Vec<u8>]) -> Result<(), GFError> {
+        if shards.len() != self.total_shards() {
+            return Err(GFError::DimensionMismatch);
+        }
+        let length = shards[0].len();
+        if shards.iter().any(|shard| shard.len() != length) {
+            return Err(GFError::DimensionMismatch);
+        }
+
+        let (data, parity) = shards.split_at_mut(self.data_shards);
+        for (p, shard) in parity.iter_mut().enumerate() {
+            shard.iter_mut().for_each(|b| *b = 0);
+            let row = self.matrix.row(self.data_shards + p);
+            for (&c, input) in row.iter().zip(data.iter()) {
+                mul_add(c, input, shard);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reconstructs the missing shards, which are marked as `None`, when the positions of all
+    /// missing shards are known. This is much cheaper than correcting errors at unknown
+    /// positions: the data shards are recovered by inverting the square submatrix of the
+    /// generator matrix that belongs to `data_shards` of the available shards, after which the
+    /// missing parity shards are encoded again.
+    ///
+    /// Returns [`GFError::TooFewShares`] if fewer than `data_shards` shards are available, and
+    /// [`GFError::DimensionMismatch`] if the number of shards is wrong or the available shards
+    /// don't all have the same length.
+    pub fn reconstruct_erasures(&self, shards: &mut [Option<Vec<u8>>]) -> Result<(), GFError> {
+        if shards.len() != self.total_shards() {
+            return Err(GFError::DimensionMismatch);
+        }
+
+        let available: Vec<usize> = (0..shards.len()).filter(|&i| shards[i].is_some()).collect();
+        if available.len() < self.data_shards {
+            return Err(GFError::TooFewShares);
+        }
+        let length = shards[available[0]].as_ref().map_or(0, Vec::len);
+        if available
+            .iter()
+            .any(|&i| shards[i].as_ref().map_or(0, Vec::len) != length)
+        {
+            return Err(GFError::DimensionMismatch);
+        }
+        if available.len() == shards.len() {
+            return Ok(());
+        }
+
+        // The chosen shards are the product of their rows of the generator matrix with the data,
+        // so the inverse of that submatrix maps them back to the data.
+        let chosen = &available[..self.data_shards];
+        let decode = self.matrix.select_rows(chosen).inverse()?;
+        for j in 0..self.data_shards {
+            if shards[j].is_none() {
+                let mut shard = vec![0; length];
+                for (t, &i) in chosen.iter().enumerate() {
+                    if let Some(input) = &shards[i] {
+                        mul_add(decode[(j, t)], input, &mut shard);
+                    }
+                }
+                shards[j] = Some(shard);
+            }
+        }
+
+        for p in self.data_shards..self.total_shards() {
+            if shards[p].is_none() {
+                let mut shard = vec![0; length];
+                let row = self.matrix.row(p);
+                for (&c, input) in row.iter().zip(shards.iter()) {
+                    if let Some(input) = input {
+                        mul_add(c, input, &mut shard);
+                    }
+                }
+                shards[p] = Some(shard);
+            }
+        }
+        Ok(())
+    }
+
+    /// Calculates the syndromes of a received codeword, which are its evaluations
+    /// <i>c</i>(&alpha;<sup>i</sup>) at the roots of the generator polynomial. They are all zero
+    /// exactly when the received word is a codeword.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the codeword has the wrong length, and
+    /// [`GFError::OutOfRange`] for a punctured code, since the removed parity symbols are needed
+    /// to evaluate the codeword.
+    pub fn syndromes(&self, codeword: &[GF]) -> Result<Vec<GF>, GFError> {
+        if codeword.len() != self.total_shards() {
+            return Err(GFError::DimensionMismatch);
+        }
+        if self.punctured != 0 {
+            return Err(GFError::OutOfRange);
+        }
+
+        let coefficients: Vec<GF> = codeword.iter().rev().copied().collect();
+        let polynomial = GFPoly::new(coefficients);
+        Ok((0..self.parity_shards)
+            .map(|i| polynomial.eval(GF::GENERATOR.pow(i as u32)))
+            .collect())
+    }
+
+    /// Counts the symbol errors in a received codeword without correcting them, which is
+    /// useful to monitor the quality of a channel or storage medium.
+    ///
+    /// The number of errors is the degree of the error locator polynomial, which
+    /// [`berlekamp_massey`] finds from the syndromes. The locator is only trusted when it has
+    /// exactly as many roots at positions of the codeword as its degree.
+    ///
+    /// Returns the number of errors, or an error if
+    /// * the codeword has the wrong length ([`GFError::DimensionMismatch`]),
+    /// * the code is punctured ([`GFError::OutOfRange`]),
+    /// * there are more than `parity_shards / 2` errors ([`GFError::TooManyErrors`]). Not every
+    ///   word with that many errors is detected, because it can be as close to another
+    ///   codeword.
+    ///
+    /// # Example
+    /// ```
+    /// # use isochronous_finite_fields::{GF, ReedSolomon};
+    /// let rs = ReedSolomon::new(4, 4).unwrap();
+    /// let mut codeword = rs.encode(&[GF(1), GF(2), GF(3), GF(4)]).unwrap();
+    /// assert_eq!(rs.count_errors(&codeword), Ok(0));
+    ///
+    /// codeword[1] += GF(0x42);
+    /// codeword[6] += GF(0x17);
+    /// assert_eq!(rs.count_errors(&codeword), Ok(2));
+    /// ```
+    pub fn count_errors(&self, codeword: &[GF]) -> Result<usize, GFError> {
+        let syndromes = self.syndromes(codeword)?;
+        let locator = berlekamp_massey(&syndromes);
+        let errors = locator.degree().unwrap_or(0);
+        if 2 * errors > self.parity_shards {
+            return Err(GFError::TooManyErrors);
+        }
+
+        // The symbol at index i is the coefficient of x^(n - 1 - i), so an error there is a
+        // root of the locator at the inverse of alpha^(n - 1 - i).
+        let n = self.total_shards();
+        let roots = (0..n)
+            .filter(|&i| {
+                let x = GF::GENERATOR.pow((255 - (n - 1 - i)) as u32);
+                locator.eval(x) == GF(0)
+            })
+            .count();
+        if roots == errors {
+            Ok(errors)
+        } else {
+            Err(GFError::TooManyErrors)
+        }
+    }
+}
+
+/// Encodes the parity of large objects stripe by stripe, so that the data never has to be in
+/// memory all at once.
+///
+/// Every call to [`update`](StreamingRsEncoder::update) takes the next stripe of every data
+/// shard, and appends the parity of that stripe to the parity shards. The result is the same as
+/// encoding the whole shards at once with [`ReedSolomon::encode_shards`].
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::StreamingRsEncoder;
+/// let mut encoder = StreamingRsEncoder::new(2, 1, 4).unwrap();
+/// encoder.update(&[b"abcd", b"efgh"]).unwrap();
+/// encoder.update(&[b"ij", b"kl"]).unwrap();
+///
+/// let parity = encoder.finish();
+/// assert_eq!(parity.len(), 1);
+/// assert_eq!(parity[0].len(), 6);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StreamingRsEncoder {
+    code: ReedSolomon,
+    stripe_len: usize,
+    short_stripe: bool,
+    parity: Vec<Vec<u8>>,
+}
+
+impl StreamingRsEncoder {
+    /// Creates an encoder for the code [`ReedSolomon::new`]`(data_shards, parity_shards)`, which
+    /// takes stripes of `stripe_len` bytes.
+    ///
+    /// Returns [`GFError::OutOfRange`] if the stripe length is zero, or if the code can't be
+    /// created.
+    pub fn new(
+        data_shards: usize,
+        parity_shards: usize,
+        stripe_len: usize,
+    ) -> Result<Self, GFError> {
+        if stripe_len == 0 {
+            return Err(GFError::OutOfRange);
+        }
+
+        Ok(StreamingRsEncoder {
+            code: ReedSolomon::new(data_shards, parity_shards)?,
+            stripe_len,
+            short_stripe: false,
+            parity: vec![Vec::new(); parity_shards],
+        })
+    }
+
+    /// Encodes the next stripe, which consists of one slice of every data shard. All slices must
+    /// have a length of `stripe_len` bytes, except for the last stripe, which may be shorter.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the number of slices is wrong, if the slices
+    /// don't all have the same length, if they are longer than `stripe_len`, or if a shorter
+    /// stripe was already encoded.
+    pub fn update(&mut self, data_shards: &[&[u8]]) -> Result<(), GFError> {
+        if data_shards.len() != self.code.data_shards() || self.short_stripe {
+            return Err(GFError::DimensionMismatch);
+        }
+        let length = data_shards[0].len();
+        if length > self.stripe_len || data_shards.iter().any(|shard| shard.len() != length) {
+            return Err(GFError::DimensionMismatch);
+        }
+        self.short_stripe = length < self.stripe_len;
+
+        for (p, shard) in self.parity.iter_mut().enumerate() {
+            let start = shard.len();
+            shard.resize(start + length, 0);
+            let row = self.code.matrix.row(self.code.data_shards + p);
+            for (&c, input) in row.iter().zip(data_shards) {
+                mul_add(c, input, &mut shard[start..]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the parity shards of all stripes that were encoded.
+    pub fn finish(self) -> Vec<Vec<u8>> {
+        self.parity
+    }
+}
+
+/// Calculates the syndromes of a codeword while its symbols arrive one at a time, so that
+/// errors are detected without buffering the codeword. The result is the same as
+/// [`ReedSolomon::syndromes`] for a code with the same number of parity symbols.
+///
+/// Syndrome `i` is the evaluation of the codeword at &alpha;<sup>i</sup>. The first symbol is
+/// the coefficient of the highest degree, so every [`push`](SyndromeAccumulator::push) is a step
+/// of Horner's method, <i>S</i><sub>i</sub> &larr; <i>S</i><sub>i</sub>
+/// &alpha;<sup>i</sup> + <i>c</i>, and costs one multiplication per syndrome.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, ReedSolomon, SyndromeAccumulator};
+/// let rs = ReedSolomon::new(3, 2).unwrap();
+/// let codeword = rs.encode(&[GF(1), GF(2), GF(3)]).unwrap();
+///
+/// let mut accumulator = SyndromeAccumulator::new(2);
+/// for &symbol in &codeword {
+///     accumulator.push(symbol);
+/// }
+/// assert_eq!(accumulator.finish(), [GF(0), GF(0)]);
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct SyndromeAccumulator {
+    /// The evaluation points &alpha;<sup>i</sup>.
+    points: Vec<GF>,
+    syndromes: Vec<GF>,
+}
+
+impl SyndromeAccumulator {
+    /// Creates an accumulator for a code with `parity_shards` parity symbols, which has that
+    /// many syndromes.
+    pub fn new(parity_shards: usize) -> Self {
+        SyndromeAccumulator {
+            points: (0..parity_shards)
+                .map(|i| GF::GENERATOR.pow(i as u32))
+                .collect(),
+            syndromes: vec![GF(0); parity_shards],
+        }
+    }
+
+    /// Consumes the next symbol of the codeword.
+    pub fn push(&mut self, symbol: GF) {
+        for (s, &x) in self.syndromes.iter_mut().zip(&self.points) {
+            *s = *s * x + symbol;
+        }
+    }
+
+    /// Returns the syndromes of the symbols that were pushed. They are all zero if no error was
+    /// detected.
+    pub fn finish(self) -> Vec<GF> {
+        self.syndromes
+    }
+}
+
+/// Interleaves the symbols of `depth` codewords, so that a burst of consecutive errors is spread
+/// over the codewords.
+///
+/// The first symbols of all codewords are sent first, then the second symbols, and so on. A
+/// burst of at most `depth * t` consecutive symbols therefore hits every codeword in at most `t`
+/// symbols, which a code that corrects `t` errors can still correct after deinterleaving.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, Interleaver};
+/// let interleaver = Interleaver::new(2).unwrap();
+/// let codewords = vec![vec![GF(1), GF(2), GF(3)], vec![GF(4), GF(5), GF(6)]];
+///
+/// let symbols = interleaver.interleave(&codewords).unwrap();
+/// assert_eq!(symbols, [GF(1), GF(4), GF(2), GF(5), GF(3), GF(6)]);
+/// assert_eq!(interleaver.deinterleave(&symbols), Ok(codewords));
+/// ```
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub struct Interleaver {
+    depth: usize,
+}
+
+impl Interleaver {
+    /// Creates an interleaver for `depth` codewords.
+    ///
+    /// Returns [`GFError::OutOfRange`] if the depth is zero.
+    pub fn new(depth: usize) -> Result<Self, GFError> {
+        if depth == 0 {
+            return Err(GFError::OutOfRange);
+        }
+        Ok(Interleaver { depth })
+    }
+
+    /// The number of codewords that are interleaved.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Interleaves the codewords into a single sequence of symbols, in which symbol `i` of
+    /// codeword `j` is at index `i * depth + j`.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if there aren't exactly `depth` codewords, or if
+    /// they don't all have the same length.
+    pub fn interleave(&self, codewords: &[Vec<GF>]) -> Result<Vec<GF>, GFError> {
+        if codewords.len() != self.depth {
+            return Err(GFError::DimensionMismatch);
+        }
+        let length = codewords[0].len();
+        if codewords.iter().any(|c| c.len() != length) {
+            return Err(GFError::DimensionMismatch);
+        }
+
+        Ok((0..length)
+            .flat_map(|i| codewords.iter().map(move |c| c[i]))
+            .collect())
+    }
+
+    /// Splits an interleaved sequence of symbols into the `depth` codewords again.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the number of symbols is not a multiple of
+    /// the depth.
+    pub fn deinterleave(&self, symbols: &[GF]) -> Result<Vec<Vec<GF>>, GFError> {
+        if symbols.len() % self.depth != 0 {
+            return Err(GFError::DimensionMismatch);
+        }
+
+        Ok((0..self.depth)
+            .map(|j| {
+                symbols
+                    .iter()
+                    .skip(j)
+                    .step_by(self.depth)
+                    .copied()
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+/// A concatenation of two Reed-Solomon codes over GF(2<sup>8</sup>), in which the symbols of
+/// every outer codeword are encoded again, `inner_data` at a time, by an inner code.
+///
+/// Both codes are evaluation codes, see [`evaluation_encode`], with the points `GF(0)`,
+/// `GF(1)`, ..., and they are decoded with [`gao_decode`]. Decoding goes from the inside out:
+/// every inner block corrects its own errors, and a block in which the inner decoder detects
+/// too many errors is erased. The outer code then corrects the remaining errors, including the
+/// blocks that the inner decoder got wrong, and an erased symbol only costs it one symbol of
+/// redundancy instead of two. Errors that are spread over many blocks are corrected by the
+/// inner code, while the outer code absorbs entire blocks that are destroyed, which together
+/// corrects patterns that neither code can correct alone.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, reed_solomon::ConcatenatedCode};
+/// // An outer (12, 4) code, with every pair of its symbols in an inner (6, 2) code.
+/// let code = ConcatenatedCode::new(4, 8, 2, 4).unwrap();
+/// let message = [GF(1), GF(2), GF(3), GF(4)];
+/// let mut codeword = code.encode(&message).unwrap();
+/// assert_eq!(codeword.len(), 36);
+///
+/// // An entire inner block is destroyed, and another block has two errors.
+/// for symbol in &mut codeword[..6] {
+///     *symbol += GF(0x55);
+/// }
+/// codeword[10] += GF(1);
+/// codeword[11] += GF(2);
+/// assert_eq!(code.decode(&codeword).unwrap(), message);
+/// ```
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub struct ConcatenatedCode {
+    outer_data: usize,
+    outer_parity: usize,
+    inner_data: usize,
+    inner_parity: usize,
+}
+
+impl ConcatenatedCode {
+    /// Creates the concatenation of an outer code with `outer_data` data symbols and
+    /// `outer_parity` parity symbols, and an inner code with `inner_data` data symbols and
+    /// `inner_parity` parity symbols.
+    ///
+    /// Returns [`GFError::OutOfRange`] if either code has no data symbols or more than 256
+    /// symbols in total, or if the length of the outer code is not a multiple of the number of
+    /// data symbols of the inner code.
+    pub fn new(
+        outer_data: usize,
+        outer_parity: usize,
+        inner_data: usize,
+        inner_parity: usize,
+    ) -> Result<Self, GFError> {
+        let outer_symbols = outer_data + outer_parity;
+        let inner_symbols = inner_data + inner_parity;
+        if outer_data == 0 || inner_data == 0 || outer_symbols > 256 || inner_symbols > 256 {
+            return Err(GFError::OutOfRange);
+        }
+        if outer_symbols % inner_data != 0 {
+            return Err(GFError::OutOfRange);
+        }
+
+        Ok(ConcatenatedCode {
+            outer_data,
+            outer_parity,
+            inner_data,
+            inner_parity,
+        })
+    }
+
+    /// The number of symbols in a message.
+    pub fn data_symbols(&self) -> usize {
+        self.outer_data
+    }
+
+    /// The number of symbols in a codeword.
+    pub fn total_symbols(&self) -> usize {
+        self.blocks() * (self.inner_data + self.inner_parity)
+    }
+
+    /// The number of inner codewords in a codeword.
+    pub fn blocks(&self) -> usize {
+        (self.outer_data + self.outer_parity) / self.inner_data
+    }
+
+    /// Encodes the message with the outer code, and then every block of `inner_data` outer
+    /// symbols with the inner code. The inner codewords follow each other in the result.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the message doesn't have exactly
+    /// [`data_symbols`](ConcatenatedCode::data_symbols) symbols.
+    pub fn encode(&self, message: &[GF]) -> Result<Vec<GF>, GFError> {
+        if message.len() != self.outer_data {
+            return Err(GFError::DimensionMismatch);
+        }
+
+        let outer_points = Self::points(self.outer_data + self.outer_parity);
+        let inner_points = Self::points(self.inner_data + self.inner_parity);
+        let outer = evaluation_encode(&GFPoly::new(message.to_vec()), &outer_points)?;
+
+        let mut codeword = Vec::with_capacity(self.total_symbols());
+        for block in outer.chunks(self.inner_data) {
+            let inner = evaluation_encode(&GFPoly::new(block.to_vec()), &inner_points)?;
+            codeword.extend_from_slice(&inner);
+        }
+        Ok(codeword)
+    }
+
+    /// Decodes a received codeword into the message. Every inner block is decoded first, and
+    /// the blocks that can't be decoded are erased before the outer code is decoded.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the codeword doesn't have exactly
+    /// [`total_symbols`](ConcatenatedCode::total_symbols) symbols, and
+    /// [`GFError::TooManyErrors`] if the outer code can't correct the remaining errors and
+    /// erasures.
+    pub fn decode(&self, received: &[GF]) -> Result<Vec<GF>, GFError> {
+        if received.len() != self.total_symbols() {
+            return Err(GFError::DimensionMismatch);
+        }
+
+        let outer_points = Self::points(self.outer_data + self.outer_parity);
+        let inner_points = Self::points(self.inner_data + self.inner_parity);
+        let mut points = Vec::with_capacity(outer_points.len());
+        let mut symbols = Vec::with_capacity(outer_points.len());
+        let inner_blocks = received.chunks(self.inner_data + self.inner_parity);
+        for (block, outer) in inner_blocks.zip(outer_points.chunks(self.inner_data)) {
+            match gao_decode(&inner_points, block, self.inner_data) {
+                Ok(inner) => {
+                    points.extend_from_slice(outer);
+                    symbols.extend_from_slice(&Self::coefficients(&inner, self.inner_data));
+                }
+                // The outer symbols of this block are erased.
+                Err(GFError::TooManyErrors) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        if points.len() < self.outer_data {
+            return Err(GFError::TooManyErrors);
+        }
+        let message = gao_decode(&points, &symbols, self.outer_data)?;
+        Ok(Self::coefficients(&message, self.outer_data))
+    }
+
+    /// The evaluation points of a code with `n` symbols.
+    fn points(n: usize) -> Vec<GF> {
+        (0..n).map(|i| GF(i as u8)).collect()
+    }
+
+    /// The first `k` coefficients of a decoded message polynomial, including the zeros at the
+    /// end.
+    fn coefficients(message: &GFPoly, k: usize) -> Vec<GF> {
+        let mut coefficients = message.coefficients().to_vec();
+        coefficients.resize(k, GF(0));
+        coefficients
+    }
+}
+
+/// Calculates `output[i] += coefficient * input[i]` for every byte.
+fn mul_add(coefficient: GF, input: &[u8], output: &mut [u8]) {
+    for (o, &i) in output.iter_mut().zip(input) {
+        *o ^= (coefficient * GF(i)).0;
+    }
+}
+
+/// Encodes a message into a generalized Reed-Solomon codeword, which consists of the
+/// evaluations of the message polynomial at the evaluation `points`.
+///
+/// The points don't have to be consecutive powers of the generator, so this also covers codes
+/// with arbitrary evaluation points. Any `k` of the symbols determine a message with `k`
+/// coefficients, see [`GFPoly::interpolate`], and [`gao_decode`] corrects errors.
+///
+/// Returns an error if
+/// * the points are not distinct ([`GFError::DuplicatePoint`]),
+/// * the message has more coefficients than there are points ([`GFError::OutOfRange`]).
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, GFPoly, reed_solomon::evaluation_encode};
+/// let message = GFPoly::new(vec![GF(4), GF(8), GF(15)]);
+/// let points = [GF(0x53), GF(0x02), GF(0xca), GF(0x11)];
+/// let codeword = evaluation_encode(&message, &points).unwrap();
+///
+/// let evaluations: Vec<_> = points.iter().copied().zip(codeword).skip(1).collect();
+/// assert_eq!(GFPoly::interpolate(&evaluations), Ok(message));
+/// ```
+pub fn evaluation_encode(message: &GFPoly, points: &[GF]) -> Result<Vec<GF>, GFError> {
+    if message.degree().is_some_and(|d| d >= points.len()) {
+        return Err(GFError::OutOfRange);
+    }
+
+    let mut seen = [false; 256];
+    for &x in points {
+        if seen[x.0 as usize] {
+            return Err(GFError::DuplicatePoint);
+        }
+        seen[x.0 as usize] = true;
+    }
+
+    Ok(message.eval_many(points))
+}
+
+/// Counts the codewords of every Hamming weight in the linear code generated by the rows of
+/// `generator`, so that element `w` of the result is the number of codewords with exactly `w`
+/// nonzero symbols. The smallest nonzero weight with a nonzero count is the minimum distance of
+/// the code.
+///
+/// All 256<sup>k</sup> codewords are enumerated for a generator matrix with `k` rows, so this
+/// is only feasible for codes with a few data symbols. The generator matrix of a
+/// [`ReedSolomon`] code has one column per data symbol, so it has to be transposed first.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{ReedSolomon, reed_solomon::weight_enumerator};
+/// let rs = ReedSolomon::new(1, 2).unwrap();
+///
+/// // The repetition code of length 3.
+/// assert_eq!(weight_enumerator(&rs.generator_matrix().transpose()), [1, 0, 0, 255]);
+/// ```
+pub fn weight_enumerator(generator: &GFMatrix) -> Vec<usize> {
+    let (k, n) = (generator.rows(), generator.cols());
+    let mut counts = vec![0; n + 1];
+    let mut message = vec![0u8; k];
+    let mut codeword = vec![GF(0); n];
+
+    loop {
+        counts[codeword.iter().filter(|&&c| c != GF(0)).count()] += 1;
+
+        // Increment the message like a counter in base 256. Changing a symbol from a to b adds
+        // (b - a) times its row to the codeword.
+        let mut i = 0;
+        loop {
+            if i == k {
+                return counts;
+            }
+            let old = message[i];
+            message[i] = old.wrapping_add(1);
+            let difference = GF(message[i]) - GF(old);
+            for (c, &g) in codeword.iter_mut().zip(generator.row(i)) {
+                *c += difference * g;
+            }
+            if message[i] != 0 {
+                break;
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Decodes a Reed-Solomon codeword with Gao's algorithm.
+///
+/// The codeword consists of the evaluations of a message polynomial with a degree lower than
+/// `k` at the distinct evaluation `points`. Up to `(points.len() - k) / 2` errors in `received`
+/// are corrected, directly from the evaluations, without calculating any syndromes. Erased
+/// symbols are handled by leaving them out, together with their evaluation points, which costs
+/// a single symbol of redundancy per erasure instead of two.
+///
+/// Returns the message polynomial, or an error if
+/// * `points` and `received` have different lengths ([`GFError::DimensionMismatch`]),
+/// * `k` is not between 1 and the number of points ([`GFError::OutOfRange`]),
+/// * the points are not distinct ([`GFError::DuplicatePoint`]),
+/// * there are too many errors to correct ([`GFError::TooManyErrors`]).
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, GFPoly, reed_solomon::gao_decode};
+/// let message = GFPoly::new(vec![GF(4), GF(8), GF(15)]);
+/// let points: Vec<GF> = (1..=7).map(GF).collect();
+/// let mut received: Vec<GF> = points.iter().map(|&x| message.eval(x)).collect();
+///
+/// // Two errors can be corrected with four symbols of redundancy.
+/// received[1] += GF(66);
+/// received[5] += GF(3);
+///
+/// assert_eq!(gao_decode(&points, &received, 3), Ok(message));
+/// ```
+pub fn gao_decode(points: &[GF], received: &[GF], k: usize) -> Result<GFPoly, GFError> {
+    let n = points.len();
+    if n != received.len() {
+        return Err(GFError::DimensionMismatch);
+    }
+    if k == 0 || k > n {
+        return Err(GFError::OutOfRange);
+    }
+
+    let evaluations: Vec<_> = points
+        .iter()
+        .copied()
+        .zip(received.iter().copied())
+        .collect();
+    let g1 = GFPoly::interpolate(&evaluations)?;
+    let g0 = points.iter().fold(GFPoly::monomial(GF(1), 0), |acc, &a| {
+        &acc * &GFPoly::new(vec![a, GF(1)])
+    });
+
+    // Run the extended Euclidean algorithm on g0 and g1, but stop as soon as the degree of the
+    // remainder drops below (n + k) / 2. Only the coefficient of g1 needs to be tracked.
+    let (mut r0, mut r1) = (g0, g1);
+    let (mut v0, mut v1) = (GFPoly::zero(), GFPoly::monomial(GF(1), 0));
+    while r1.degree().is_some_and(|d| 2 * d >= n + k) {
+        let (q, r) = r0.div_rem(&r1);
+        let v = &v0 - &(&q * &v1);
+        r0 = core::mem::replace(&mut r1, r);
+        v0 = core::mem::replace(&mut v1, v);
+    }
+
+    // The coefficient v1 is the error locator polynomial, which divides the remainder exactly
+    // when the errors can be corrected.
+    let (message, remainder) = r1.div_rem(&v1);
+    if remainder.is_zero() && message.degree().is_none_or(|d| d < k) {
+        Ok(message)
+    } else {
+        Err(GFError::TooManyErrors)
+    }
+}
+
+/// Finds the shortest linear feedback shift register that generates the `sequence`, with the
+/// Berlekamp-Massey algorithm.
+///
+/// Returns the connection polynomial <i>C</i>(<i>x</i>) = 1 + <i>c</i><sub>1</sub><i>x</i> +
+/// &middot;&middot;&middot; + <i>c</i><sub>L</sub><i>x</i><sup>L</sup>, for which
+/// `sequence[j] == c_1 * sequence[j - 1] + ... + c_L * sequence[j - L]` for every `j >= L`.
+/// Applied to the syndromes of a Reed-Solomon codeword, this is the error locator polynomial,
+/// which has the inverses of the error positions as its roots. The length <i>L</i> of the
+/// register can be larger than the degree when the last coefficients are zero.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, GFPoly, reed_solomon::berlekamp_massey};
+/// // A geometric sequence is generated by a register of length one.
+/// let sequence: Vec<GF> = (0..6).map(|i| GF(7) * GF(0x35).pow(i)).collect();
+///
+/// assert_eq!(berlekamp_massey(&sequence), GFPoly::new(vec![GF(1), GF(0x35)]));
+/// ```
+pub fn berlekamp_massey(sequence: &[GF]) -> GFPoly {
+    let mut connection = vec![GF(1)];
+    let mut previous = vec![GF(1)];
+    let mut length = 0;
+    let mut shift = 1;
+    let mut previous_discrepancy = GF(1);
+
+    for n in 0..sequence.len() {
+        let discrepancy = (1..=length).fold(sequence[n], |acc, i| {
+            acc + connection.get(i).copied().unwrap_or(GF(0)) * sequence[n - i]
+        });
+        if discrepancy == GF(0) {
+            shift += 1;
+            continue;
+        }
+
+        // connection -= discrepancy / previous_discrepancy * x^shift * previous
+        let factor = discrepancy * previous_discrepancy.multiplicative_inverse();
+        let updated = {
+            let mut updated = connection.clone();
+            updated.resize(updated.len().max(previous.len() + shift), GF(0));
+            for (i, &p) in previous.iter().enumerate() {
+                updated[i + shift] -= factor * p;
+            }
+            updated
+        };
+
+        if 2 * length <= n {
+            previous = core::mem::replace(&mut connection, updated);
+            length = n + 1 - length;
+            previous_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            connection = updated;
+            shift += 1;
+        }
+    }
+    GFPoly::new(connection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data_shards(count: usize, length: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|i| (0..length).map(|j| (i * 71 + j * 13 + 5) as u8).collect())
+            .collect()
+    }
+
+    fn encoded_shards(rs: &ReedSolomon, length: usize) -> Vec<Vec<u8>> {
+        let mut shards = data_shards(rs.data_shards(), length);
+        shards.resize(rs.total_shards(), vec![0; length]);
+        rs.encode_shards(&mut shards).unwrap();
+        shards
+    }
+
+    #[test]
+    fn streaming_matches_one_shot_encode() {
+        let rs = ReedSolomon::new(5, 3).unwrap();
+        let shards = encoded_shards(&rs, 100);
+
+        let mut encoder = StreamingRsEncoder::new(5, 3, 16).unwrap();
+        for start in (0..100).step_by(16) {
+            let end = usize::min(start + 16, 100);
+            let stripe: Vec<&[u8]> = shards[..5].iter().map(|s| &s[start..end]).collect();
+            encoder.update(&stripe).unwrap();
+        }
+
+        assert_eq!(encoder.finish(), &shards[5..]);
+    }
+
+    #[test]
+    fn streaming_invalid_stripes() {
+        assert!(StreamingRsEncoder::new(2, 1, 0).is_err());
+
+        let mut encoder = StreamingRsEncoder::new(2, 1, 4).unwrap();
+        let long: &[u8] = &[1, 2, 3, 4, 5];
+        let short: &[u8] = &[1, 2];
+        assert_eq!(encoder.update(&[short]), Err(GFError::DimensionMismatch));
+        assert_eq!(
+            encoder.update(&[long, long]),
+            Err(GFError::DimensionMismatch)
+        );
+        assert_eq!(
+            encoder.update(&[short, &long[..3]]),
+            Err(GFError::DimensionMismatch)
+        );
+
+        // Only the last stripe may be shorter.
+        encoder.update(&[short, short]).unwrap();
+        assert_eq!(
+            encoder.update(&[&long[..4], &long[..4]]),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn evaluation_encode_and_interpolate() {
+        let message = GFPoly::new(vec![GF(0x53), GF(0xca), GF(0x01), GF(0xff)]);
+        let points = [GF(0), GF(7), GF(0x80), GF(0x13), GF(0xfe), GF(1), GF(0x40)];
+        let codeword = evaluation_encode(&message, &points).unwrap();
+
+        for start in 0..4 {
+            let evaluations: Vec<_> = points
+                .iter()
+                .copied()
+                .zip(codeword.iter().copied())
+                .skip(start)
+                .take(4)
+                .collect();
+            assert_eq!(GFPoly::interpolate(&evaluations), Ok(message.clone()));
+        }
+
+        let mut received = codeword.clone();
+        received[2] += GF(0x21);
+        assert_eq!(gao_decode(&points, &received, 4), Ok(message));
+    }
+
+    #[test]
+    fn evaluation_encode_invalid_points() {
+        let message = GFPoly::new(vec![GF(1), GF(2), GF(3)]);
+        assert_eq!(
+            evaluation_encode(&message, &[GF(1), GF(2), GF(1)]),
+            Err(GFError::DuplicatePoint)
+        );
+        assert_eq!(
+            evaluation_encode(&message, &[GF(1), GF(2)]),
+            Err(GFError::OutOfRange)
+        );
+    }
+
+    /// The smallest number of nonzero symbols of a nonzero codeword, for codes with two data
+    /// symbols.
+    fn minimum_distance(rs: &ReedSolomon) -> usize {
+        (1..=0xffffu32)
+            .map(|m| {
+                let codeword = rs.encode(&[GF((m >> 8) as u8), GF(m as u8)]).unwrap();
+                codeword.iter().filter(|&&c| c != GF(0)).count()
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn shortened_code_matches_smaller_code() {
+        let rs = ReedSolomon::new(10, 4).unwrap();
+        assert_eq!(rs.with_shortened(0), Ok(rs.clone()));
+        assert_eq!(rs.with_shortened(7), ReedSolomon::new(3, 4));
+        assert_eq!(rs.with_shortened(10), Err(GFError::OutOfRange));
+    }
+
+    #[test]
+    fn shortened_code_keeps_distance() {
+        // The distance of 5 means that two errors can still be corrected.
+        let shortened = ReedSolomon::new(5, 4).unwrap().with_shortened(3).unwrap();
+        assert_eq!(minimum_distance(&shortened), 5);
+    }
+
+    #[test]
+    fn punctured_code_loses_distance() {
+        let rs = ReedSolomon::new(2, 4).unwrap();
+        let punctured = rs.with_punctured(1).unwrap();
+        assert_eq!(minimum_distance(&punctured), 4);
+        assert_eq!(punctured.total_shards(), 5);
+        assert_eq!(rs.with_punctured(5), Err(GFError::OutOfRange));
+
+        let data = [GF(0x53), GF(0xca)];
+        let codeword = rs.encode(&data).unwrap();
+        assert_eq!(punctured.encode(&data).unwrap(), &codeword[..5]);
+    }
+
+    #[test]
+    fn reconstruct_punctured_and_shortened() {
+        let rs = ReedSolomon::new(8, 4)
+            .unwrap()
+            .with_shortened(2)
+            .unwrap()
+            .with_punctured(1)
+            .unwrap();
+        let shards = encoded_shards(&rs, 20);
+
+        for a in 0..9 {
+            for b in a + 1..9 {
+                for c in b + 1..9 {
+                    let mut received: Vec<_> = shards.iter().cloned().map(Some).collect();
+                    received[a] = None;
+                    received[b] = None;
+                    received[c] = None;
+
+                    rs.reconstruct_erasures(&mut received).unwrap();
+                    let received: Vec<_> = received.into_iter().map(Option::unwrap).collect();
+                    assert_eq!(received, shards);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn new_validates_parameters() {
+        assert!(ReedSolomon::new(1, 0).is_ok());
+        assert!(ReedSolomon::new(200, 55).is_ok());
+        assert_eq!(ReedSolomon::new(0, 4), Err(GFError::OutOfRange));
+        assert_eq!(ReedSolomon::new(200, 56), Err(GFError::OutOfRange));
+    }
+
+    #[test]
+    fn codewords_are_multiples_of_the_generator() {
+        let rs = ReedSolomon::new(5, 4).unwrap();
+        let data = [GF(0x53), GF(0xca), GF(0x00), GF(0x01), GF(0xff)];
+        let codeword = rs.encode(&data).unwrap();
+
+        assert_eq!(&codeword[..5], &data);
+        let polynomial = GFPoly::new(codeword.iter().rev().copied().collect());
+        assert!(polynomial.div_rem(rs.generator_polynomial()).1.is_zero());
+        for i in 0..4 {
+            assert_eq!(polynomial.eval(GF::GENERATOR.pow(i)), GF(0));
+        }
+        assert_eq!(rs.encode(&data[..4]), Err(GFError::DimensionMismatch));
+    }
+
+    #[test]
+    fn encode_shards_matches_encode() {
+        let rs = ReedSolomon::new(4, 3).unwrap();
+        let shards = encoded_shards(&rs, 20);
+
+        for i in 0..20 {
+            let data: Vec<GF> = shards[..4].iter().map(|s| GF(s[i])).collect();
+            let codeword: Vec<GF> = shards.iter().map(|s| GF(s[i])).collect();
+            assert_eq!(rs.encode(&data), Ok(codeword));
+        }
+    }
+
+    #[test]
+    fn reconstruct_every_erasure_pattern() {
+        let rs = ReedSolomon::new(6, 3).unwrap();
+        let shards = encoded_shards(&rs, 50);
+
+        for a in 0..9 {
+            for b in a..9 {
+                for c in b..9 {
+                    let mut received: Vec<_> = shards.iter().cloned().map(Some).collect();
+                    received[a] = None;
+                    received[b] = None;
+                    received[c] = None;
+
+                    rs.reconstruct_erasures(&mut received).unwrap();
+                    let received: Vec<_> = received.into_iter().map(Option::unwrap).collect();
+                    assert_eq!(received, shards);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reconstruct_too_many_erasures() {
+        let rs = ReedSolomon::new(4, 2).unwrap();
+        let mut received: Vec<_> = encoded_shards(&rs, 8).into_iter().map(Some).collect();
+        received[0] = None;
+        received[2] = None;
+        received[5] = None;
+
+        assert_eq!(
+            rs.reconstruct_erasures(&mut received),
+            Err(GFError::TooFewShares)
+        );
+        assert_eq!(
+            rs.reconstruct_erasures(&mut received[..5]),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn reconstruct_shards_of_different_lengths() {
+        let rs = ReedSolomon::new(2, 2).unwrap();
+        let mut received = vec![Some(vec![1, 2]), None, Some(vec![3]), None];
+        assert_eq!(
+            rs.reconstruct_erasures(&mut received),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    fn codeword(message: &GFPoly, points: &[GF]) -> Vec<GF> {
+        points.iter().map(|&x| message.eval(x)).collect()
+    }
+
+    #[test]
+    fn gao_decode_without_errors() {
+        let message = GFPoly::new(vec![GF(0x01), GF(0x02), GF(0x03), GF(0x04)]);
+        let points: Vec<GF> = (1..=10).map(GF).collect();
+        let received = codeword(&message, &points);
+
+        assert_eq!(gao_decode(&points, &received, 4), Ok(message));
+    }
+
+    #[test]
+    fn gao_decode_corrects_errors() {
+        let message = GFPoly::new(vec![GF(0x53), GF(0xca), GF(0x00), GF(0x8d)]);
+        let points: Vec<GF> = (0..12).map(|i| GF(3 * i + 1)).collect();
+        let mut received = codeword(&message, &points);
+        received[0] += GF(0x01);
+        received[4] += GF(0xff);
+        received[7] += GF(0x10);
+        received[11] += GF(0x9a);
+
+        assert_eq!(gao_decode(&points, &received, 4), Ok(message));
+    }
+
+    #[test]
+    fn gao_decode_matches_berlekamp_massey() {
+        let message = GFPoly::new(vec![GF(0x22), GF(0x81), GF(0x93), GF(0x5b)]);
+        let points: Vec<GF> = (1..=12).map(GF).collect();
+        let (n, k) = (points.len(), 4);
+
+        // The dual of the evaluation code is an evaluation code at the same points, with the
+        // column multipliers v_j = 1 / prod_{l != j} (x_j - x_l). The syndromes
+        // S_i = sum_j v_j x_j^i r_j then only depend on the errors, and Berlekamp-Massey finds
+        // the locator prod_j (1 - x_j x) of the error positions.
+        let multipliers: Vec<GF> = (0..n)
+            .map(|j| {
+                (0..n)
+                    .filter(|&l| l != j)
+                    .fold(GF(1), |acc, l| acc * (points[j] - points[l]))
+                    .multiplicative_inverse()
+            })
+            .collect();
+        let syndromes = |word: &[GF]| -> Vec<GF> {
+            (0..n - k)
+                .map(|i| {
+                    (0..n).fold(GF(0), |sum, j| {
+                        sum + multipliers[j] * points[j].pow(i as u32) * word[j]
+                    })
+                })
+                .collect()
+        };
+
+        let codeword = codeword(&message, &points);
+        assert!(syndromes(&codeword).iter().all(|&s| s == GF(0)));
+
+        for errors in [&[3][..], &[0, 11], &[2, 5, 9], &[1, 4, 7, 10]] {
+            let mut received = codeword.clone();
+            for (e, &j) in errors.iter().enumerate() {
+                received[j] += GF(0x13 + 0x20 * e as u8);
+            }
+
+            let decoded = gao_decode(&points, &received, k).unwrap();
+            let gao_positions: Vec<usize> = (0..n)
+                .filter(|&j| decoded.eval(points[j]) != received[j])
+                .collect();
+
+            let locator = berlekamp_massey(&syndromes(&received));
+            let bm_positions: Vec<usize> = (0..n)
+                .filter(|&j| locator.eval(points[j].multiplicative_inverse()) == GF(0))
+                .collect();
+
+            assert_eq!(decoded, message);
+            assert_eq!(gao_positions, errors);
+            assert_eq!(bm_positions, gao_positions);
+            assert_eq!(locator.degree(), Some(errors.len()));
+        }
+    }
+
+    #[test]
+    fn gao_decode_with_erasures_and_errors() {
+        let message = GFPoly::new(vec![GF(0x22), GF(0x81), GF(0x93), GF(0x5b)]);
+        let points: Vec<GF> = (1..=12).map(GF).collect();
+        let mut received = codeword(&message, &points);
+        received[2] += GF(0x13);
+        received[3] += GF(0x57);
+        received[10] += GF(0x02);
+
+        // Erase the symbols at positions 5 and 6 by leaving them out.
+        let kept: Vec<usize> = (0..12).filter(|&i| i != 5 && i != 6).collect();
+        let points: Vec<GF> = kept.iter().map(|&i| points[i]).collect();
+        let received: Vec<GF> = kept.iter().map(|&i| received[i]).collect();
+
+        assert_eq!(gao_decode(&points, &received, 4), Ok(message));
+    }
+
+    #[test]
+    fn gao_decode_invalid_input() {
+        let points = [GF(1), GF(2), GF(3)];
+        assert_eq!(
+            gao_decode(&points, &[GF(0); 2], 1),
+            Err(GFError::DimensionMismatch)
+        );
+        assert_eq!(
+            gao_decode(&points, &[GF(0); 3], 0),
+            Err(GFError::OutOfRange)
+        );
+        assert_eq!(
+            gao_decode(&points, &[GF(0); 3], 4),
+            Err(GFError::OutOfRange)
+        );
+        assert_eq!(
+            gao_decode(&[GF(1), GF(1)], &[GF(0); 2], 1),
+            Err(GFError::DuplicatePoint)
+        );
+    }
+
+    #[test]
+    fn weight_enumerator_of_mds_code() {
+        // An MDS code of length 5 and dimension 2 has minimum distance 4, and exactly
+        // C(5, 4) * 255 codewords of that weight.
+        let rs = ReedSolomon::new(2, 3).unwrap();
+        let counts = weight_enumerator(&rs.generator_matrix().transpose());
+
+        assert_eq!(counts, [1, 0, 0, 0, 5 * 255, 65536 - 1 - 5 * 255]);
+        assert_eq!(counts.iter().sum::<usize>(), 65536);
+        assert_eq!(
+            counts.iter().skip(1).position(|&c| c > 0).map(|w| w + 1),
+            Some(minimum_distance(&rs))
+        );
+    }
+
+    #[test]
+    fn weight_enumerator_of_degenerate_codes() {
+        assert_eq!(weight_enumerator(&GFMatrix::zero(0, 3)), [1, 0, 0, 0]);
+        assert_eq!(weight_enumerator(&GFMatrix::zero(1, 2)), [256, 0, 0]);
+    }
+
+    #[test]
+    fn berlekamp_massey_finds_shortest_register() {
+        assert_eq!(berlekamp_massey(&[]), GFPoly::monomial(GF(1), 0));
+        assert_eq!(berlekamp_massey(&[GF(0); 5]), GFPoly::monomial(GF(1), 0));
+
+        // s[j] = s[j - 1] + s[j - 2], started from 1, 1.
+        let mut sequence = vec![GF(1), GF(1)];
+        for j in 2..10 {
+            sequence.push(sequence[j - 1] + sequence[j - 2]);
+        }
+        assert_eq!(
+            berlekamp_massey(&sequence),
+            GFPoly::new(vec![GF(1), GF(1), GF(1)])
+        );
+    }
+
+    #[test]
+    fn count_injected_errors() {
+        let rs = ReedSolomon::new(20, 8).unwrap();
+        let data: Vec<GF> = (0..20).map(|i| GF(i * 12 + 11)).collect();
+        let codeword = rs.encode(&data).unwrap();
+
+        let positions = [27, 3, 14, 0, 20];
+        let values = [GF(0x01), GF(0x9c), GF(0x40), GF(0xfe), GF(0x35)];
+        for errors in 0..=4 {
+            let mut received = codeword.clone();
+            for (&p, &v) in positions.iter().zip(&values).take(errors) {
+                received[p] += v;
+            }
+            assert_eq!(rs.count_errors(&received), Ok(errors));
+        }
+    }
+
+    #[test]
+    fn count_errors_beyond_capacity() {
+        let rs = ReedSolomon::new(10, 4).unwrap();
+        let mut received = rs.encode(&[GF(0x55); 10]).unwrap();
+        received[1] += GF(0x10);
+        received[5] += GF(0x20);
+        received[12] += GF(0x30);
+
+        assert_eq!(rs.count_errors(&received), Err(GFError::TooManyErrors));
+        assert_eq!(
+            rs.count_errors(&received[..13]),
+            Err(GFError::DimensionMismatch)
+        );
+        assert_eq!(
+            rs.with_punctured(1).unwrap().count_errors(&received[..13]),
+            Err(GFError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn count_errors_in_shortened_code() {
+        let rs = ReedSolomon::new(200, 6)
+            .unwrap()
+            .with_shortened(190)
+            .unwrap();
+        let mut received = rs.encode(&[GF(0xaa); 10]).unwrap();
+        received[0] += GF(0x01);
+        received[15] += GF(0x80);
+
+        assert_eq!(rs.count_errors(&received), Ok(2));
+    }
+
+    #[test]
+    fn concatenated_code_round_trip() {
+        let code = ConcatenatedCode::new(5, 7, 3, 2).unwrap();
+        assert_eq!(code.blocks(), 4);
+        assert_eq!(code.total_symbols(), 20);
+
+        let message = [GF(0x53), GF(0), GF(0xca), GF(0xff), GF(0)];
+        let codeword = code.encode(&message).unwrap();
+        assert_eq!(codeword.len(), code.total_symbols());
+        assert_eq!(code.decode(&codeword).unwrap(), message);
+    }
+
+    #[test]
+    fn concatenated_code_beats_both_codes() {
+        // The outer (12, 4) code corrects 4 errors or 8 erasures, the inner (6, 2) code
+        // corrects 2 errors per block.
+        let code = ConcatenatedCode::new(4, 8, 2, 4).unwrap();
+        let message = [GF(0x12), GF(0x34), GF(0x56), GF(0x78)];
+        let codeword = code.encode(&message).unwrap();
+
+        // Two errors in each of the first four blocks, and the last two blocks destroyed.
+        let mut received = codeword.clone();
+        for block in 0..4 {
+            received[block * 6 + 1] += GF(0x11 + block as u8);
+            received[block * 6 + 4] += GF(0x80);
+        }
+        for (i, symbol) in received[24..].iter_mut().enumerate() {
+            *symbol += GF((i * 12 + 11) as u8);
+        }
+        let errors = codeword
+            .iter()
+            .zip(&received)
+            .filter(|(a, b)| a != b)
+            .count();
+        assert_eq!(errors, 20);
+
+        // The inner code can't correct a destroyed block on its own.
+        let inner_points = ConcatenatedCode::points(6);
+        for block in [24..30, 30..36] {
+            let decoded = gao_decode(&inner_points, &received[block.clone()], 2)
+                .and_then(|m| evaluation_encode(&m, &inner_points));
+            assert!(decoded.is_err() || decoded.unwrap() != codeword[block]);
+        }
+        // A single code of the same length and rate, (36, 4), only corrects 16 errors, so it
+        // fails on the same error pattern.
+        let single_points = ConcatenatedCode::points(36);
+        let single_message = GFPoly::new(message.to_vec());
+        let mut single_received = evaluation_encode(&single_message, &single_points).unwrap();
+        for ((r, &c), &x) in single_received.iter_mut().zip(&codeword).zip(&received) {
+            *r += x - c;
+        }
+        let single = gao_decode(&single_points, &single_received, 4);
+        assert!(single != Ok(single_message));
+
+        assert_eq!(code.decode(&received).unwrap(), message);
+    }
+
+    #[test]
+    fn concatenated_code_too_many_errors() {
+        let code = ConcatenatedCode::new(4, 2, 2, 2).unwrap();
+        let mut received = code.encode(&[GF(1), GF(2), GF(3), GF(4)]).unwrap();
+        for (i, symbol) in received.iter_mut().enumerate() {
+            *symbol += GF((i * 37 + 1) as u8);
+        }
+        assert_eq!(code.decode(&received), Err(GFError::TooManyErrors));
+    }
+
+    #[test]
+    fn concatenated_code_invalid_parameters() {
+        assert_eq!(ConcatenatedCode::new(0, 4, 2, 2), Err(GFError::OutOfRange));
+        assert_eq!(ConcatenatedCode::new(4, 4, 0, 2), Err(GFError::OutOfRange));
+        assert_eq!(ConcatenatedCode::new(4, 3, 2, 2), Err(GFError::OutOfRange));
+        assert_eq!(
+            ConcatenatedCode::new(200, 57, 1, 0),
+            Err(GFError::OutOfRange)
+        );
+        assert!(ConcatenatedCode::new(200, 56, 2, 254).is_ok());
+
+        let code = ConcatenatedCode::new(4, 4, 2, 2).unwrap();
+        assert_eq!(code.encode(&[GF(0); 3]), Err(GFError::DimensionMismatch));
+        assert_eq!(code.decode(&[GF(0); 15]), Err(GFError::DimensionMismatch));
+    }
+
+    #[test]
+    fn streaming_syndromes_match_batch() {
+        let rs = ReedSolomon::new(10, 6).unwrap();
+        let data: Vec<GF> = (0..10).map(|i| GF((i * 12 + 11) as u8)).collect();
+        let mut codeword = rs.encode(&data).unwrap();
+
+        for error in [
+            None,
+            Some((0, GF(0x53))),
+            Some((9, GF(1))),
+            Some((15, GF(0xff))),
+        ] {
+            if let Some((i, e)) = error {
+                codeword[i] += e;
+            }
+            let mut accumulator = SyndromeAccumulator::new(6);
+            for &symbol in &codeword {
+                accumulator.push(symbol);
+            }
+            let syndromes = accumulator.finish();
+            assert_eq!(syndromes, rs.syndromes(&codeword).unwrap());
+            assert_eq!(syndromes.iter().all(|&s| s == GF(0)), error.is_none());
+        }
+        assert_eq!(SyndromeAccumulator::new(0).finish(), []);
+    }
+
+    #[test]
+    fn interleaving_corrects_bursts() {
+        // Every codeword corrects t = 2 errors, so four interleaved codewords correct a burst
+        // of eight symbols.
+        let (depth, k, n, t) = (4, 6, 10, 2);
+        let interleaver = Interleaver::new(depth).unwrap();
+        let points: Vec<GF> = (1..=n as u8).map(GF).collect();
+        let messages: Vec<GFPoly> = (0..depth)
+            .map(|j| GFPoly::new((0..k).map(|i| GF((j * 40 + i * 7 + 1) as u8)).collect()))
+            .collect();
+        let codewords: Vec<Vec<GF>> = messages.iter().map(|m| codeword(m, &points)).collect();
+        let symbols = interleaver.interleave(&codewords).unwrap();
+
+        for start in 0..=symbols.len() - depth * t {
+            let mut received = symbols.clone();
+            for s in &mut received[start..start + depth * t] {
+                *s += GF(0xa5);
+            }
+
+            let received = interleaver.deinterleave(&received).unwrap();
+            for (r, m) in received.iter().zip(&messages) {
+                assert_eq!(gao_decode(&points, r, k).as_ref(), Ok(m));
+            }
+        }
+    }
+
+    #[test]
+    fn interleaver_invalid_input() {
+        assert_eq!(Interleaver::new(0), Err(GFError::OutOfRange));
+
+        let interleaver = Interleaver::new(3).unwrap();
+        assert_eq!(interleaver.depth(), 3);
+        assert_eq!(
+            interleaver.interleave(&[vec![GF(1)], vec![GF(2)]]),
+            Err(GFError::DimensionMismatch)
+        );
+        assert_eq!(
+            interleaver.interleave(&[vec![GF(1)], vec![GF(2)], vec![]]),
+            Err(GFError::DimensionMismatch)
+        );
+        assert_eq!(
+            interleaver.deinterleave(&[GF(1); 4]),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+}
@@ -0,0 +1,168 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Polynomial hashes over a sliding window.
+
+use crate::GF;
+
+/// A polynomial hash of the last `window` bytes of a stream, which is updated in constant time
+/// when the window slides by one byte. This is the building block of content-defined chunking,
+/// which cuts a stream wherever the hash of the window has a certain value.
+///
+/// The hash of the bytes <i>b</i><sub>0</sub>, ..., <i>b</i><sub>n-1</sub> is the polynomial
+/// <i>b</i><sub>0</sub> <i>B</i><sup>n-1</sup> + ... + <i>b</i><sub>n-2</sub> <i>B</i> +
+/// <i>b</i><sub>n-1</sub> in the base <i>B</i>, so the oldest byte has the highest power. When
+/// the window slides, the contribution of the outgoing byte is removed with the precomputed
+/// power <i>B</i><sup>n-1</sup>, the remaining sum is multiplied by <i>B</i>, and the incoming
+/// byte is added.
+///
+/// The hash has only 256 possible values, so it is not collision resistant, and the bytes that
+/// leave the window have to be kept by the caller.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, RollingHash};
+/// let data = b"abcd";
+/// let mut hash = RollingHash::new(GF(3), 3);
+/// for &b in &data[..3] {
+///     hash.push(b);
+/// }
+/// hash.roll(data[0], data[3]);
+///
+/// let mut expected = RollingHash::new(GF(3), 3);
+/// for &b in &data[1..] {
+///     expected.push(b);
+/// }
+/// assert_eq!(hash.hash(), expected.hash());
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct RollingHash {
+    base: GF,
+    window: usize,
+    /// The base to the power `window - 1`, which is the factor of the oldest byte.
+    top: GF,
+    hash: GF,
+}
+
+impl RollingHash {
+    /// Creates the hash of an empty window of `window` bytes with the base `base`. Use
+    /// [`push`](RollingHash::push) to fill the first window.
+    ///
+    /// The base should have a high multiplicative order, such as [`GF::GENERATOR`], so that the
+    /// positions in the window have different powers.
+    ///
+    /// # Panics
+    /// Panics if `window` is zero.
+    pub fn new(base: GF, window: usize) -> Self {
+        assert!(window > 0, "the window must contain at least one byte");
+        // Nonzero elements have an order that divides 255, which keeps the exponent small.
+        let top = if base == GF(0) {
+            GF((window == 1) as u8)
+        } else {
+            base.pow(((window - 1) % 255) as u32)
+        };
+        RollingHash {
+            base,
+            window,
+            top,
+            hash: GF(0),
+        }
+    }
+
+    /// The base of the polynomial hash.
+    pub fn base(&self) -> GF {
+        self.base
+    }
+
+    /// The number of bytes in the window.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// The hash of the current window.
+    pub fn hash(&self) -> GF {
+        self.hash
+    }
+
+    /// Appends `in_byte` without removing a byte, which is how the first window is filled.
+    pub fn push(&mut self, in_byte: u8) {
+        self.hash = self.hash * self.base + GF(in_byte);
+    }
+
+    /// Slides the window by one byte: `out_byte`, the oldest byte in the window, is removed and
+    /// `in_byte` is appended. This costs two multiplications, whatever the size of the window.
+    pub fn roll(&mut self, out_byte: u8, in_byte: u8) {
+        let remaining = self.hash - GF(out_byte) * self.top;
+        self.hash = remaining * self.base + GF(in_byte);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The hash of a window, calculated from scratch with Horner's method.
+    fn window_hash(base: GF, bytes: &[u8]) -> GF {
+        bytes.iter().fold(GF(0), |hash, &b| hash * base + GF(b))
+    }
+
+    #[test]
+    fn rolling_matches_recomputation() {
+        let mut data = [0u8; 600];
+        for (i, b) in data.iter_mut().enumerate() {
+            *b = (i * 12 + 11) as u8 ^ (i >> 3) as u8;
+        }
+
+        for window in [1, 2, 16, 48, 255, 256, 300] {
+            for base in [GF::GENERATOR, GF(2), GF(0x53)] {
+                let mut hash = RollingHash::new(base, window);
+                for &b in &data[..window] {
+                    hash.push(b);
+                }
+                assert_eq!(hash.hash(), window_hash(base, &data[..window]));
+
+                for start in 1..=data.len() - window {
+                    hash.roll(data[start - 1], data[start + window - 1]);
+                    let expected = window_hash(base, &data[start..start + window]);
+                    assert_eq!(hash.hash(), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn zero_base_only_keeps_the_last_byte() {
+        let mut hash = RollingHash::new(GF(0), 4);
+        for b in [1, 2, 3, 4] {
+            hash.push(b);
+        }
+        assert_eq!(hash.hash(), GF(4));
+        hash.roll(1, 9);
+        assert_eq!(hash.hash(), GF(9));
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_window() {
+        let _ = RollingHash::new(GF::GENERATOR, 0);
+    }
+}
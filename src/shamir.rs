@@ -0,0 +1,308 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Shamir's secret sharing over GF(2<sup>8</sup>).
+//!
+//! A share is a pair `(x, y)` of bytes, where `y` is the evaluation of a secret polynomial at
+//! the nonzero point `x`. The secret is the constant term of the polynomial, and any `threshold`
+//! shares with distinct `x` coordinates determine the polynomial.
+
+#[cfg(feature = "alloc")]
+use crate::GFError;
+use crate::GF;
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+/// Evaluates the polynomial of the lowest degree through all `shares` at the point `x`, with
+/// Lagrange interpolation. The `x` coordinates of the shares must be distinct.
+fn interpolate_at(shares: &[(u8, u8)], x: GF) -> GF {
+    let mut result = GF(0);
+
+    for (j, &(x_j, y_j)) in shares.iter().enumerate() {
+        let mut basis = GF(1);
+        for (m, &(x_m, _)) in shares.iter().enumerate() {
+            if m != j {
+                basis *= (x - GF(x_m)) / (GF(x_j) - GF(x_m));
+            }
+        }
+        result += GF(y_j) * basis;
+    }
+
+    result
+}
+
+/// Checks whether all shares lie on a single polynomial with a degree lower than `threshold`.
+///
+/// The polynomial is interpolated from the first `threshold` shares, and every other share is
+/// checked against it. This detects shares that were corrupted or tampered with before the
+/// secret is reconstructed. Returns `false` if there are fewer than `threshold` shares, if
+/// `threshold` is zero, or if the `x` coordinates of the shares are not distinct and nonzero.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::shamir::verify_shares;
+/// // Shares of the polynomial 42 + 7x
+/// let mut shares = [(1, 45), (2, 36), (3, 35)];
+/// assert!(verify_shares(&shares, 2));
+///
+/// shares[2].1 ^= 1;
+/// assert!(!verify_shares(&shares, 2));
+/// ```
+pub fn verify_shares(shares: &[(u8, u8)], threshold: usize) -> bool {
+    if threshold == 0 || shares.len() < threshold {
+        return false;
+    }
+
+    let mut seen = [false; 256];
+    for &(x, _) in shares {
+        if x == 0 || seen[x as usize] {
+            return false;
+        }
+        seen[x as usize] = true;
+    }
+
+    let (base, rest) = shares.split_at(threshold);
+    rest.iter()
+        .all(|&(x, y)| interpolate_at(base, GF(x)) == GF(y))
+}
+
+/// Splits a secret into `n` shares, such that any `k` of them reconstruct the secret with
+/// [`combine_bytes`], while fewer than `k` shares reveal nothing about it.
+///
+/// Every byte of the secret is shared independently, with its own random polynomial of degree
+/// `k - 1`. All bytes of a participant share the same `x` coordinate, so the participants get
+/// the coordinates `1, 2, ..., n`. Since `0` can't be used as a coordinate, there can be at most
+/// 255 shares.
+///
+/// The random coefficients are taken from `rng`, which must be a cryptographically secure
+/// random number generator.
+///
+/// Returns [`GFError::OutOfRange`] if `k` is zero, if `k` is larger than `n`, or if `n` is
+/// larger than 255.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::shamir::{combine_bytes, split_bytes};
+/// # let mut state = 0x2545_f491u32;
+/// # let rng = || { state ^= state << 13; state ^= state >> 17; state ^= state << 5; state as u8 };
+/// let shares = split_bytes(b"secret", 5, 3, rng).unwrap();
+///
+/// assert_eq!(combine_bytes(&shares[1..4]).unwrap(), b"secret");
+/// ```
+#[cfg(feature = "alloc")]
+pub fn split_bytes(
+    secret: &[u8],
+    n: usize,
+    k: usize,
+    mut rng: impl FnMut() -> u8,
+) -> Result<Vec<(u8, Vec<u8>)>, GFError> {
+    if k == 0 || k > n || n > 255 {
+        return Err(GFError::OutOfRange);
+    }
+
+    let mut shares: Vec<(u8, Vec<u8>)> = (1..=n as u8)
+        .map(|x| (x, Vec::with_capacity(secret.len())))
+        .collect();
+    let mut coefficients = vec![GF(0); k];
+
+    for &byte in secret {
+        coefficients[0] = GF(byte);
+        for c in &mut coefficients[1..] {
+            *c = GF(rng());
+        }
+
+        for (x, y) in &mut shares {
+            let value = coefficients
+                .iter()
+                .rev()
+                .fold(GF(0), |acc, &c| acc * GF(*x) + c);
+            y.push(value.0);
+        }
+    }
+
+    Ok(shares)
+}
+
+/// Reconstructs a secret from shares that were created with [`split_bytes`]. At least as many
+/// shares as the threshold are needed, otherwise the result is garbage.
+///
+/// Returns an error if
+/// * there are no shares ([`GFError::TooFewShares`]),
+/// * the shares have different lengths ([`GFError::DimensionMismatch`]),
+/// * an `x` coordinate is zero ([`GFError::OutOfRange`]),
+/// * the `x` coordinates are not distinct ([`GFError::DuplicatePoint`]).
+#[cfg(feature = "alloc")]
+pub fn combine_bytes(shares: &[(u8, Vec<u8>)]) -> Result<Vec<u8>, GFError> {
+    let length = shares.first().ok_or(GFError::TooFewShares)?.1.len();
+
+    let mut seen = [false; 256];
+    for (x, y) in shares {
+        if y.len() != length {
+            return Err(GFError::DimensionMismatch);
+        }
+        if *x == 0 {
+            return Err(GFError::OutOfRange);
+        }
+        if seen[*x as usize] {
+            return Err(GFError::DuplicatePoint);
+        }
+        seen[*x as usize] = true;
+    }
+
+    // The Lagrange basis polynomials evaluated at zero are the same for every byte.
+    let weights: Vec<GF> = shares
+        .iter()
+        .map(|&(x_j, _)| {
+            shares
+                .iter()
+                .filter(|&&(x_m, _)| x_m != x_j)
+                .fold(GF(1), |acc, &(x_m, _)| acc * GF(x_m) / (GF(x_j) - GF(x_m)))
+        })
+        .collect();
+
+    Ok((0..length)
+        .map(|i| {
+            shares
+                .iter()
+                .zip(&weights)
+                .fold(GF(0), |acc, ((_, y), &w)| acc + GF(y[i]) * w)
+                .0
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::test_rng;
+
+    /// Creates shares of the polynomial with the given coefficients at x = 1, 2, ..., n.
+    fn shares(coefficients: &[u8], n: u8) -> [(u8, u8); 8] {
+        let mut shares = [(0, 0); 8];
+        for x in 1..=n {
+            let y = coefficients
+                .iter()
+                .rev()
+                .fold(GF(0), |acc, &c| acc * GF(x) + GF(c));
+            shares[x as usize - 1] = (x, y.0);
+        }
+        shares
+    }
+
+    #[test]
+    fn interpolate_recovers_secret() {
+        let shares = shares(&[0x53, 0xca, 0x01], 3);
+        assert_eq!(interpolate_at(&shares[..3], GF(0)), GF(0x53));
+    }
+
+    #[test]
+    fn consistent_shares_pass() {
+        let shares = shares(&[0x53, 0xca, 0x01], 8);
+        assert!(verify_shares(&shares, 3));
+        assert!(verify_shares(&shares[2..5], 3));
+    }
+
+    #[test]
+    fn tampered_share_is_rejected() {
+        for i in 0..8 {
+            let mut shares = shares(&[0x53, 0xca, 0x01], 8);
+            shares[i].1 ^= 0x80;
+            assert!(!verify_shares(&shares, 3));
+        }
+    }
+
+    #[test]
+    fn invalid_share_sets_are_rejected() {
+        let shares = shares(&[0x53, 0xca, 0x01], 8);
+        assert!(!verify_shares(&shares[..2], 3));
+        assert!(!verify_shares(&shares, 0));
+        assert!(!verify_shares(&[(1, 2), (1, 2), (3, 4)], 2));
+        assert!(!verify_shares(&[(0, 2), (1, 2), (3, 4)], 2));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn split_and_combine_bytes() {
+        let secret = b"The quick brown fox jumps over the lazy dog";
+        let shares = split_bytes(secret, 6, 4, test_rng()).unwrap();
+        assert_eq!(shares.len(), 6);
+
+        for subset in [&shares[..4], &shares[2..], &shares[1..5]] {
+            assert_eq!(combine_bytes(subset).unwrap(), secret);
+        }
+
+        let subset = [
+            shares[5].clone(),
+            shares[0].clone(),
+            shares[3].clone(),
+            shares[2].clone(),
+        ];
+        assert_eq!(combine_bytes(&subset).unwrap(), secret);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn split_bytes_shares_are_consistent() {
+        let shares = split_bytes(&[0x53, 0xca], 5, 3, test_rng()).unwrap();
+        for i in 0..2 {
+            let byte_shares: Vec<_> = shares.iter().map(|(x, y)| (*x, y[i])).collect();
+            assert!(verify_shares(&byte_shares, 3));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn too_few_shares_give_wrong_secret() {
+        let shares = split_bytes(b"secret", 5, 3, test_rng()).unwrap();
+        assert_ne!(combine_bytes(&shares[..2]).unwrap(), b"secret");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn combine_invalid_shares() {
+        assert_eq!(combine_bytes(&[]), Err(GFError::TooFewShares));
+        assert_eq!(
+            combine_bytes(&[(1, vec![1, 2]), (2, vec![3])]),
+            Err(GFError::DimensionMismatch)
+        );
+        assert_eq!(
+            combine_bytes(&[(0, vec![1]), (1, vec![3])]),
+            Err(GFError::OutOfRange)
+        );
+        assert_eq!(
+            combine_bytes(&[(1, vec![1]), (1, vec![3])]),
+            Err(GFError::DuplicatePoint)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn split_bytes_invalid_parameters() {
+        for (n, k) in [(256, 3), (3, 4), (3, 0)] {
+            assert_eq!(
+                split_bytes(b"secret", n, k, test_rng()),
+                Err(GFError::OutOfRange)
+            );
+        }
+    }
+}
@@ -0,0 +1,863 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Operations on slices of elements of GF(2<sup>8</sup>).
+
+use crate::{nonzero_mask, GFError, GF};
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+/// Evaluates the polynomial with the coefficients `coeffs` at `x` with Horner's method, as a
+/// `const fn`. Element `i` of `coeffs` is the coefficient of <i>x</i><sup>i</sup>, like in
+/// `GFPoly`, so evaluation results can be baked into `const` items.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::slice::eval_const;
+/// // x^2 + 3x + 2 at x = 2
+/// const VALUE: u8 = eval_const(&[2, 3, 1], 2);
+/// assert_eq!(VALUE, 0);
+/// ```
+pub const fn eval_const(coeffs: &[u8], x: u8) -> u8 {
+    let mut result = GF(0);
+    let mut i = coeffs.len();
+    while i > 0 {
+        i -= 1;
+        result = GF(result.mul_const(GF(x)).0 ^ coeffs[i]);
+    }
+    result.0
+}
+
+/// Selects `table[index]` in constant time.
+///
+/// Every element of the table is read, no matter the value of `index`, so the index is not
+/// leaked through the memory access pattern. The running time only depends on the length of the
+/// table. An `index` beyond the end of the table results in `GF(0)`. Tables with more than 256
+/// elements can only be indexed in their first 256 elements.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::ct_select};
+/// let table = [GF(3), GF(141), GF(28)];
+///
+/// assert_eq!(ct_select(&table, 1), GF(141));
+/// assert_eq!(ct_select(&table, 3), GF(0));
+/// ```
+pub fn ct_select(table: &[GF], index: u8) -> GF {
+    let mut selected = 0;
+
+    for (i, element) in table.iter().take(256).enumerate() {
+        // The mask is only all ones when i is equal to the index.
+        selected |= !nonzero_mask(i as u8 ^ index) & element.0;
+    }
+
+    GF(selected)
+}
+
+/// Reinterprets a slice of bytes as a slice of field elements, without copying.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::from_bytes};
+/// assert_eq!(from_bytes(&[1, 2, 3]), &[GF(1), GF(2), GF(3)]);
+/// ```
+pub fn from_bytes(bytes: &[u8]) -> &[GF] {
+    // SAFETY: GF is a #[repr(transparent)] wrapper around u8, so it has the same size,
+    // alignment and validity as u8.
+    unsafe { core::slice::from_raw_parts(bytes.as_ptr() as *const GF, bytes.len()) }
+}
+
+/// Reinterprets a mutable slice of bytes as a mutable slice of field elements, without copying.
+pub fn from_bytes_mut(bytes: &mut [u8]) -> &mut [GF] {
+    // SAFETY: GF is a #[repr(transparent)] wrapper around u8, so it has the same size,
+    // alignment and validity as u8.
+    unsafe { core::slice::from_raw_parts_mut(bytes.as_mut_ptr() as *mut GF, bytes.len()) }
+}
+
+/// Reinterprets a slice of field elements as a slice of bytes, without copying.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::as_bytes};
+/// assert_eq!(as_bytes(&[GF(1), GF(2), GF(3)]), &[1, 2, 3]);
+/// ```
+pub fn as_bytes(elements: &[GF]) -> &[u8] {
+    // SAFETY: GF is a #[repr(transparent)] wrapper around u8, so it has the same size,
+    // alignment and validity as u8.
+    unsafe { core::slice::from_raw_parts(elements.as_ptr() as *const u8, elements.len()) }
+}
+
+/// Reinterprets a mutable slice of field elements as a mutable slice of bytes, without copying.
+pub fn as_bytes_mut(elements: &mut [GF]) -> &mut [u8] {
+    // SAFETY: GF is a #[repr(transparent)] wrapper around u8, so it has the same size,
+    // alignment and validity as u8.
+    unsafe { core::slice::from_raw_parts_mut(elements.as_mut_ptr() as *mut u8, elements.len()) }
+}
+
+/// Adds all `buffers` element by element, and writes the sums to `output`. In GF(2<sup>8</sup>),
+/// this is the exclusive or (XOR) of all buffers, which is the P parity of RAID. Without any
+/// buffers, `output` is set to zero.
+///
+/// Returns [`GFError::DimensionMismatch`] if a buffer has a different length than `output`.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::xor_all};
+/// let mut parity = [GF(0); 2];
+/// xor_all(&[&[GF(1), GF(2)], &[GF(4), GF(8)], &[GF(3), GF(3)]], &mut parity).unwrap();
+///
+/// assert_eq!(parity, [GF(6), GF(9)]);
+/// ```
+pub fn xor_all(buffers: &[&[GF]], output: &mut [GF]) -> Result<(), GFError> {
+    if buffers.iter().any(|buffer| buffer.len() != output.len()) {
+        return Err(GFError::DimensionMismatch);
+    }
+
+    output.iter_mut().for_each(|o| *o = GF(0));
+    for buffer in buffers {
+        for (o, &b) in output.iter_mut().zip(buffer.iter()) {
+            *o += b;
+        }
+    }
+    Ok(())
+}
+
+/// Counts the positions in which `a` and `b` have different symbols, which is the Hamming
+/// distance between two words. The distance between a received word and the codeword that was
+/// sent is the number of symbol errors.
+///
+/// Every position is compared with a mask instead of a branch, so the running time only
+/// depends on the length.
+///
+/// # Panics
+/// Panics if the slices have different lengths.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::hamming_distance};
+/// assert_eq!(hamming_distance(&[GF(1), GF(2), GF(3)], &[GF(1), GF(5), GF(0)]), 2);
+/// ```
+pub fn hamming_distance(a: &[GF], b: &[GF]) -> usize {
+    assert_eq!(a.len(), b.len(), "the words must have the same length");
+
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (nonzero_mask(x.0 ^ y.0) & 1) as usize)
+        .sum()
+}
+
+/// Adds `c` to every element of `slice` in place, which is an exclusive or with the same byte.
+/// Adding the same scalar twice restores the original elements.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::add_scalar};
+/// let mut v = [GF(1), GF(2), GF(3)];
+/// add_scalar(&mut v, GF(3));
+///
+/// assert_eq!(v, [GF(2), GF(1), GF(0)]);
+/// ```
+pub fn add_scalar(slice: &mut [GF], c: GF) {
+    for x in slice {
+        *x += c;
+    }
+}
+
+/// Packs eight elements into a single integer, for compact storage and bit manipulations on
+/// all lanes at once. Lane `i` is byte `i` in little-endian order, so element 0 is in the lowest
+/// byte and element 7 in the highest byte.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::{pack, unpack}};
+/// let v = [GF(1), GF(2), GF(3), GF(4), GF(5), GF(6), GF(7), GF(8)];
+///
+/// assert_eq!(pack(&v), 0x0807_0605_0403_0201);
+/// assert_eq!(unpack(pack(&v)), v);
+/// ```
+pub fn pack(v: &[GF; 8]) -> u64 {
+    u64::from_le_bytes(v.map(|x| x.0))
+}
+
+/// Unpacks an integer into eight elements, with the lane order of [`pack`].
+pub fn unpack(x: u64) -> [GF; 8] {
+    x.to_le_bytes().map(GF)
+}
+
+/// Transposes 64 elements into 8 bit planes, for bitsliced implementations that process one bit
+/// of 64 elements with every word operation.
+///
+/// Bit `j` of plane `i` is bit `i` of element `j`. Addition is an exclusive or of the planes,
+/// and a multiplication by <i>x</i> moves plane `i` to plane `i + 1` with an exclusive or of the
+/// top plane into planes 0, 1, 3 and 4. [`untranspose_bitslice`] is the inverse.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::{transpose_bitslice, untranspose_bitslice}};
+/// let mut input = [GF(0); 64];
+/// input[5] = GF(0b1001);
+/// let planes = transpose_bitslice(&input);
+///
+/// assert_eq!(planes, [1 << 5, 0, 0, 1 << 5, 0, 0, 0, 0]);
+/// assert_eq!(untranspose_bitslice(&planes), input);
+/// ```
+pub fn transpose_bitslice(input: &[GF; 64]) -> [u64; 8] {
+    let mut planes = [0; 8];
+    for (i, plane) in planes.iter_mut().enumerate() {
+        for (j, x) in input.iter().enumerate() {
+            *plane |= u64::from((x.0 >> i) & 1) << j;
+        }
+    }
+    planes
+}
+
+/// Transposes 8 bit planes back into 64 elements, the inverse of [`transpose_bitslice`].
+pub fn untranspose_bitslice(planes: &[u64; 8]) -> [GF; 64] {
+    let mut output = [GF(0); 64];
+    for (j, x) in output.iter_mut().enumerate() {
+        for (i, plane) in planes.iter().enumerate() {
+            x.0 |= (((plane >> j) & 1) as u8) << i;
+        }
+    }
+    output
+}
+
+/// The precomputed effect of multiplying by a fixed coefficient, which multiplies the eight
+/// lanes of a [`pack`]ed integer at once. Create it with [`GF::precompute_scale`].
+///
+/// Multiplying by a constant is linear over GF(2), so the product is the sum of the constants
+/// <i>c</i> <i>x</i><sup>i</sup> for the bits `i` that are set in an element. Those eight
+/// constants are stored broadcast to all lanes, and every bit costs a mask, an `and` and an
+/// exclusive or for all lanes together. There are no table lookups, so the running time does
+/// not depend on the lanes or on the coefficient.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::{pack, unpack}};
+/// let table = GF(2).precompute_scale();
+/// let v = [GF(1), GF(2), GF(3), GF(4), GF(5), GF(6), GF(7), GF(0x80)];
+///
+/// assert_eq!(unpack(table.apply_u64(pack(&v)))[7], GF(0x1b));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct ScaleTable {
+    /// Element `i` is the coefficient times x^i, in every lane.
+    broadcast: [u64; 8],
+}
+
+impl ScaleTable {
+    /// Precomputes the multiplication by `coefficient`.
+    pub fn new(coefficient: GF) -> Self {
+        let mut power = coefficient;
+        let mut broadcast = [0; 8];
+        for b in &mut broadcast {
+            *b = u64::from(power.0) * LOW_BITS;
+            power *= GF(2);
+        }
+        ScaleTable { broadcast }
+    }
+
+    /// Multiplies all eight lanes of a [`pack`]ed integer by the coefficient.
+    pub fn apply_u64(&self, packed: u64) -> u64 {
+        let mut result = 0;
+        for (i, &b) in self.broadcast.iter().enumerate() {
+            // All bits of a lane are set if bit i of that lane is set.
+            let mask = ((packed >> i) & LOW_BITS) * 0xff;
+            result ^= mask & b;
+        }
+        result
+    }
+}
+
+/// The lowest bit of every lane of a packed integer.
+const LOW_BITS: u64 = 0x0101_0101_0101_0101;
+
+/// Multiplies every element of `input` by `coefficient`, and writes the products to `output`.
+///
+/// This is the basic operation of encoding with a generator matrix. Whole chunks of eight
+/// elements are [`pack`]ed and multiplied together with a [`ScaleTable`], and the remaining
+/// tail is multiplied one element at a time, so the slices can have any length. Both paths are
+/// isochronous, so the running time only depends on the length of the slices.
+///
+/// Returns [`GFError::DimensionMismatch`] if the slices have different lengths.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::scale};
+/// let mut output = [GF(0); 3];
+/// scale(GF(2), &[GF(1), GF(3), GF(0x80)], &mut output).unwrap();
+///
+/// assert_eq!(output, [GF(2), GF(6), GF(0x1b)]);
+/// ```
+pub fn scale(coefficient: GF, input: &[GF], output: &mut [GF]) -> Result<(), GFError> {
+    if input.len() != output.len() {
+        return Err(GFError::DimensionMismatch);
+    }
+
+    let table = coefficient.precompute_scale();
+    let mut input_chunks = input.chunks_exact(8);
+    let mut output_chunks = output.chunks_exact_mut(8);
+    for (o, i) in (&mut output_chunks).zip(&mut input_chunks) {
+        let mut lanes = [GF(0); 8];
+        lanes.copy_from_slice(i);
+        o.copy_from_slice(&unpack(table.apply_u64(pack(&lanes))));
+    }
+
+    for (o, &i) in output_chunks
+        .into_remainder()
+        .iter_mut()
+        .zip(input_chunks.remainder())
+    {
+        *o = coefficient * i;
+    }
+    Ok(())
+}
+
+/// Multiplies every element of `input` by `coefficient`, and adds the products to `output`.
+///
+/// This is the inner loop of encoding a parity shard, which is the sum of the scaled data
+/// shards. Like [`scale`], the running time only depends on the length of the slices.
+///
+/// Returns [`GFError::DimensionMismatch`] if the slices have different lengths.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::mul_add};
+/// let mut output = [GF(1), GF(1), GF(1)];
+/// mul_add(GF(2), &[GF(1), GF(3), GF(0x80)], &mut output).unwrap();
+///
+/// assert_eq!(output, [GF(3), GF(7), GF(0x1a)]);
+/// ```
+pub fn mul_add(coefficient: GF, input: &[GF], output: &mut [GF]) -> Result<(), GFError> {
+    if input.len() != output.len() {
+        return Err(GFError::DimensionMismatch);
+    }
+
+    for (o, &i) in output.iter_mut().zip(input) {
+        *o += coefficient * i;
+    }
+    Ok(())
+}
+
+/// Calculates the multiplicative inverses of all `elements` with Montgomery's trick, and writes
+/// them to `inverses`. Zero elements have `GF(0)` as inverse, like in
+/// [`GF::multiplicative_inverse`].
+///
+/// Only a single [`GF::multiplicative_inverse`] is needed, for the product of all elements.
+/// Every element then costs three multiplications, which is much cheaper than inverting them
+/// one by one. Zeros are replaced by ones in the product with masks, so the running time only
+/// depends on the length of the slices.
+///
+/// Returns [`GFError::DimensionMismatch`] if the slices have different lengths.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::batch_inverse};
+/// let elements = [GF(0x53), GF(0), GF(2)];
+/// let mut inverses = [GF(0); 3];
+/// batch_inverse(&elements, &mut inverses).unwrap();
+///
+/// assert_eq!(inverses, [GF(0xca), GF(0), GF(0x8d)]);
+/// ```
+pub fn batch_inverse(elements: &[GF], inverses: &mut [GF]) -> Result<(), GFError> {
+    if elements.len() != inverses.len() {
+        return Err(GFError::DimensionMismatch);
+    }
+    let nonzero = |e: GF| {
+        let mask = nonzero_mask(e.0);
+        GF((mask & e.0) | (!mask & 1))
+    };
+
+    // inverses[i] holds the product of all elements before i.
+    let mut product = GF(1);
+    for (inverse, &e) in inverses.iter_mut().zip(elements) {
+        *inverse = product;
+        product *= nonzero(e);
+    }
+
+    // Walking backwards, inverse is the inverse of the product of all elements up to i.
+    let mut inverse = product.multiplicative_inverse();
+    for (output, &e) in inverses.iter_mut().zip(elements).rev() {
+        let result = *output * inverse;
+        inverse *= nonzero(e);
+        *output = GF(nonzero_mask(e.0) & result.0);
+    }
+    Ok(())
+}
+
+/// The linear combination of `vectors` with the coefficients `coefficients`, which is the sum
+/// of `coefficients[i] * vectors[i]`, element by element. This is the inner loop of matrix
+/// multiplication and of encoding with a generator matrix. Without any vectors, the result is
+/// empty.
+///
+/// Returns [`GFError::DimensionMismatch`] if the number of coefficients differs from the number
+/// of vectors, or if the vectors don't all have the same length.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::linear_combination};
+/// let a = [GF(1), GF(2)];
+/// let b = [GF(3), GF(4)];
+/// let sum = linear_combination(&[GF(2), GF(1)], &[&a, &b]).unwrap();
+///
+/// assert_eq!(sum, vec![GF(2) + GF(3), GF(4) + GF(4)]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn linear_combination(coefficients: &[GF], vectors: &[&[GF]]) -> Result<Vec<GF>, GFError> {
+    if coefficients.len() != vectors.len() {
+        return Err(GFError::DimensionMismatch);
+    }
+    let length = vectors.first().map_or(0, |v| v.len());
+    if vectors.iter().any(|v| v.len() != length) {
+        return Err(GFError::DimensionMismatch);
+    }
+
+    let mut result = vec![GF(0); length];
+    for (&c, vector) in coefficients.iter().zip(vectors) {
+        for (r, &v) in result.iter_mut().zip(vector.iter()) {
+            *r += c * v;
+        }
+    }
+    Ok(result)
+}
+
+/// The linear (acyclic) convolution of two sequences, which is equal to the coefficients of the
+/// product of the polynomials with the coefficients `a` and `b`. The result has
+/// `a.len() + b.len() - 1` elements, or none if either sequence is empty.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, slice::convolve};
+/// let c = convolve(&[GF(1), GF(2)], &[GF(3), GF(4), GF(5)]);
+///
+/// assert_eq!(c, vec![GF(3), GF(4) + GF(6), GF(5) + GF(8), GF(10)]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn convolve(a: &[GF], b: &[GF]) -> Vec<GF> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut result = vec![GF(0); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (r, &y) in result[i..].iter_mut().zip(b) {
+            *r += x * y;
+        }
+    }
+    result
+}
+
+/// The cyclic convolution of two sequences of the same length `n`, where element `k` of the
+/// result is the sum of `a[i] * b[j]` over all `i + j` that are equal to `k` modulo `n`.
+///
+/// Returns [`GFError::DimensionMismatch`] if the sequences have different lengths.
+#[cfg(feature = "alloc")]
+pub fn cyclic_convolve(a: &[GF], b: &[GF]) -> Result<Vec<GF>, GFError> {
+    if a.len() != b.len() {
+        return Err(GFError::DimensionMismatch);
+    }
+
+    let n = a.len();
+    let mut result = vec![GF(0); n];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            result[(i + j) % n] += x * y;
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn byte_casts_preserve_values() {
+        let bytes: [u8; 5] = [0x00, 0x01, 0x53, 0xca, 0xff];
+        let elements = from_bytes(&bytes);
+
+        assert_eq!(elements.len(), bytes.len());
+        for (element, &byte) in elements.iter().zip(&bytes) {
+            assert_eq!(*element, GF(byte));
+        }
+        assert_eq!(as_bytes(elements), &bytes);
+        assert!(from_bytes(&[]).is_empty());
+    }
+
+    #[test]
+    fn mutable_byte_casts() {
+        let mut bytes = [0x57, 0x13];
+        from_bytes_mut(&mut bytes)[0] *= GF(0x83);
+        assert_eq!(bytes, [0xc1, 0x13]);
+
+        let mut elements = [GF(1), GF(2)];
+        as_bytes_mut(&mut elements)[1] = 0xff;
+        assert_eq!(elements, [GF(1), GF(0xff)]);
+    }
+
+    #[test]
+    fn ct_select_matches_indexing() {
+        let table = [GF(0x53), GF(0xca), GF(0x00), GF(0xff), GF(0x01), GF(0x8d)];
+        for (i, &element) in table.iter().enumerate() {
+            assert_eq!(ct_select(&table, i as u8), element);
+        }
+    }
+
+    #[test]
+    fn ct_select_full_table() {
+        let mut table = [GF(0); 256];
+        for (i, element) in table.iter_mut().enumerate() {
+            *element = GF(255 - i as u8);
+        }
+        for index in 0u8..=255u8 {
+            assert_eq!(ct_select(&table, index), table[index as usize]);
+        }
+    }
+
+    #[test]
+    fn ct_select_out_of_range() {
+        assert_eq!(ct_select(&[GF(1), GF(2)], 2), GF(0));
+        assert_eq!(ct_select(&[], 0), GF(0));
+    }
+
+    #[test]
+    fn xor_all_matches_sequential_xor() {
+        let a = [GF(0x53), GF(0xca), GF(0x01), GF(0xff)];
+        let b = [GF(0x57), GF(0x83), GF(0x13), GF(0xff)];
+        let c = [GF(0x02), GF(0x00), GF(0x80), GF(0x11)];
+        let mut output = [GF(0x42); 4];
+        xor_all(&[&a, &b, &c], &mut output).unwrap();
+
+        for i in 0..4 {
+            assert_eq!(output[i], a[i] + b[i] + c[i]);
+            assert_eq!(output[i].0, a[i].0 ^ b[i].0 ^ c[i].0);
+        }
+    }
+
+    #[test]
+    fn xor_all_without_buffers() {
+        let mut output = [GF(0x42); 4];
+        xor_all(&[], &mut output).unwrap();
+        assert_eq!(output, [GF(0); 4]);
+    }
+
+    #[test]
+    fn xor_all_length_mismatch() {
+        let mut output = [GF(0x42); 4];
+        assert_eq!(
+            xor_all(&[&[GF(1); 4], &[GF(1); 3]], &mut output),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn hamming_distance_counts_differences() {
+        let a = [GF(0x53), GF(0xca), GF(0x00), GF(0xff), GF(0x01)];
+        assert_eq!(hamming_distance(&a, &a), 0);
+        assert_eq!(hamming_distance(&[], &[]), 0);
+
+        let mut b = a;
+        for x in &mut b {
+            *x += GF(0x80);
+        }
+        assert_eq!(hamming_distance(&a, &b), 5);
+
+        let mut c = a;
+        c[1] = GF(0);
+        c[4] += GF(1);
+        assert_eq!(hamming_distance(&a, &c), 2);
+        assert_eq!(hamming_distance(&c, &a), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn hamming_distance_length_mismatch() {
+        let _ = hamming_distance(&[GF(0); 3], &[GF(0); 4]);
+    }
+
+    #[test]
+    fn add_scalar_is_an_involution() {
+        let original = [GF(0x53), GF(0xca), GF(0x00), GF(0xff)];
+
+        let mut v = original;
+        add_scalar(&mut v, GF(0));
+        assert_eq!(v, original);
+
+        add_scalar(&mut v, GF(0x1b));
+        assert_eq!(v, original.map(|x| x + GF(0x1b)));
+        add_scalar(&mut v, GF(0x1b));
+        assert_eq!(v, original);
+
+        add_scalar(&mut [], GF(1));
+    }
+
+    #[test]
+    fn pack_round_trip() {
+        let v = [
+            GF(0x53),
+            GF(0xca),
+            GF(0x00),
+            GF(0x01),
+            GF(0xff),
+            GF(0x80),
+            GF(0x13),
+            GF(0x57),
+        ];
+        assert_eq!(unpack(pack(&v)), v);
+        assert_eq!(pack(&unpack(0x0123_4567_89ab_cdef)), 0x0123_4567_89ab_cdef);
+    }
+
+    #[test]
+    fn pack_lane_order() {
+        for lane in 0..8 {
+            let mut v = [GF(0); 8];
+            v[lane] = GF(0xa5);
+            assert_eq!(pack(&v), 0xa5 << (8 * lane));
+            assert_eq!(unpack(0xa5 << (8 * lane)), v);
+        }
+    }
+
+    #[test]
+    fn bitslice_round_trip() {
+        let mut rng = crate::test_rng();
+        let mut input = [GF(0); 64];
+        for x in &mut input {
+            *x = GF(rng());
+        }
+
+        let planes = transpose_bitslice(&input);
+        assert_eq!(untranspose_bitslice(&planes), input);
+        assert_eq!(transpose_bitslice(&untranspose_bitslice(&planes)), planes);
+        for (i, plane) in planes.iter().enumerate() {
+            for (j, x) in input.iter().enumerate() {
+                assert_eq!((plane >> j) & 1, u64::from((x.0 >> i) & 1));
+            }
+        }
+    }
+
+    #[test]
+    fn bitslice_planes_of_constant_input() {
+        assert_eq!(transpose_bitslice(&[GF(0xff); 64]), [u64::MAX; 8]);
+        assert_eq!(
+            transpose_bitslice(&[GF(0x04); 64]),
+            [0, 0, u64::MAX, 0, 0, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn scale_matches_multiplication() {
+        let input: [GF; 256] = core::array::from_fn(|i| GF(i as u8));
+        let mut output = [GF(0); 256];
+        for coefficient in [0x02, 0x53, 0xca, 0xff] {
+            scale(GF(coefficient), &input, &mut output).unwrap();
+            for (&o, &i) in output.iter().zip(&input) {
+                assert_eq!(o, GF(coefficient) * i);
+            }
+        }
+    }
+
+    #[test]
+    fn scale_by_zero_and_one() {
+        let input = [GF(0x53), GF(0xca), GF(0x01), GF(0xff)];
+        let mut output = [GF(0x11); 4];
+
+        scale(GF(1), &input, &mut output).unwrap();
+        assert_eq!(output, input);
+        scale(GF(0), &input, &mut output).unwrap();
+        assert_eq!(output, [GF(0); 4]);
+    }
+
+    #[test]
+    fn scale_table_matches_multiplication() {
+        for coefficient in [0x00, 0x01, 0x02, 0x53, 0xca, 0xff] {
+            let table = GF(coefficient).precompute_scale();
+            for start in (0..256).step_by(8) {
+                let lanes: [GF; 8] = core::array::from_fn(|i| GF((start + i) as u8));
+                let scaled = unpack(table.apply_u64(pack(&lanes)));
+                for (&s, &l) in scaled.iter().zip(&lanes) {
+                    assert_eq!(s, GF(coefficient) * l);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scale_handles_partial_chunks() {
+        let mut input = [GF(0); 40];
+        for (i, x) in input.iter_mut().enumerate() {
+            *x = GF((i * 12 + 11) as u8);
+        }
+        for len in [0, 1, 7, 8, 9, 16, 23, 40] {
+            for c in [GF(0), GF(1), GF(2), GF(0x53), GF(0xff)] {
+                let mut output = [GF(0xaa); 40];
+                scale(c, &input[..len], &mut output[..len]).unwrap();
+                for (&o, &i) in output[..len].iter().zip(&input[..len]) {
+                    assert_eq!(o, c * i);
+                }
+                assert!(output[len..].iter().all(|&o| o == GF(0xaa)));
+            }
+        }
+    }
+
+    #[test]
+    fn mul_add_accumulates_products() {
+        let input: [GF; 256] = core::array::from_fn(|i| GF(i as u8));
+        let mut output: [GF; 256] = core::array::from_fn(|i| GF(255 - i as u8));
+        mul_add(GF(0x53), &input, &mut output).unwrap();
+        for (i, &o) in output.iter().enumerate() {
+            assert_eq!(o, GF(255 - i as u8) + GF(0x53) * GF(i as u8));
+        }
+        assert_eq!(
+            mul_add(GF(2), &input[..3], &mut output),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn scale_length_mismatch() {
+        let mut output = [GF(0); 3];
+        assert_eq!(
+            scale(GF(2), &[GF(1); 4], &mut output),
+            Err(GFError::DimensionMismatch)
+        );
+        // A mismatch that only shows up in the tail after the whole chunks.
+        let mut output = [GF(0); 8];
+        assert_eq!(
+            scale(GF(2), &[GF(1); 9], &mut output),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn linear_combination_matches_accumulation() {
+        let a = [GF(0x53), GF(0xca), GF(0x01)];
+        let b = [GF(0x57), GF(0x83), GF(0x13)];
+        let c = [GF(0x02), GF(0x00), GF(0xff)];
+        let coefficients = [GF(0x11), GF(0x80), GF(0x03)];
+
+        let mut expected = vec![GF(0); 3];
+        for (&coefficient, vector) in coefficients.iter().zip([&a, &b, &c]) {
+            for (e, &v) in expected.iter_mut().zip(vector) {
+                *e += coefficient * v;
+            }
+        }
+        assert_eq!(
+            linear_combination(&coefficients, &[&a, &b, &c]),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn linear_combination_single_term() {
+        let a = [GF(0x53), GF(0xca), GF(0x01)];
+        let mut scaled = [GF(0); 3];
+        scale(GF(0x80), &a, &mut scaled).unwrap();
+
+        assert_eq!(linear_combination(&[GF(0x80)], &[&a]), Ok(scaled.to_vec()));
+        assert_eq!(linear_combination(&[], &[]), Ok(vec![]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn linear_combination_dimension_mismatch() {
+        let a = [GF(1), GF(2)];
+        let b = [GF(3)];
+        assert_eq!(
+            linear_combination(&[GF(1), GF(1)], &[&a, &b]),
+            Err(GFError::DimensionMismatch)
+        );
+        assert_eq!(
+            linear_combination(&[GF(1)], &[&a, &a]),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn convolve_matches_polynomial_multiplication() {
+        let a = [GF(0x53), GF(0xca), GF(0x00), GF(0x01)];
+        let b = [GF(0x57), GF(0x83), GF(0x13)];
+        let c = convolve(&a, &b);
+
+        assert_eq!(c.len(), a.len() + b.len() - 1);
+        let product = crate::GFPoly::new(a.to_vec()) * crate::GFPoly::new(b.to_vec());
+        assert_eq!(c, product.coefficients());
+        assert!(convolve(&a, &[]).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn cyclic_convolve_wraps_around() {
+        let a = [GF(0x53), GF(0xca), GF(0x02)];
+        let b = [GF(0x57), GF(0x83), GF(0x13)];
+        let linear = convolve(&a, &b);
+
+        // Folding the linear convolution modulo x^3 - 1 gives the cyclic convolution.
+        let expected = vec![linear[0] + linear[3], linear[1] + linear[4], linear[2]];
+        assert_eq!(cyclic_convolve(&a, &b), Ok(expected));
+        assert_eq!(
+            cyclic_convolve(&a, &b[..2]),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn eval_const_in_const_context() {
+        const COEFFS: [u8; 4] = [0x53, 0x00, 0xca, 0x01];
+        const VALUES: [u8; 3] = [
+            eval_const(&COEFFS, 0x00),
+            eval_const(&COEFFS, 0x01),
+            eval_const(&COEFFS, 0x02),
+        ];
+        assert_eq!(VALUES[0], 0x53);
+        assert_eq!(VALUES[1], 0x53 ^ 0xca ^ 0x01);
+        assert_eq!(eval_const(&[], 0x35), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn eval_const_matches_poly_eval() {
+        let coeffs = [0x53, 0x00, 0xca, 0x01, 0x8d, 0xff];
+        let p = crate::GFPoly::new(coeffs.iter().map(|&c| GF(c)).collect());
+        for x in 0u8..=255u8 {
+            assert_eq!(GF(eval_const(&coeffs, x)), p.eval(GF(x)));
+        }
+    }
+
+    #[test]
+    fn batch_inverse_matches_inverse() {
+        let elements: [GF; 256] = core::array::from_fn(|i| GF((i * 37 + 11) as u8));
+        let mut inverses = [GF(0); 256];
+        batch_inverse(&elements, &mut inverses).unwrap();
+        for (&e, &inverse) in elements.iter().zip(&inverses) {
+            assert_eq!(inverse, e.multiplicative_inverse());
+        }
+
+        batch_inverse(&[], &mut []).unwrap();
+        assert_eq!(
+            batch_inverse(&[GF(1)], &mut []),
+            Err(GFError::DimensionMismatch)
+        );
+    }
+}
@@ -0,0 +1,215 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Incremental solving of linear systems over GF(2<sup>8</sup>).
+
+use crate::{GFError, GF};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Solves a system of linear equations that arrive one at a time, such as the generator rows
+/// and symbols of shards that are received over a network.
+///
+/// The equations are kept in reduced row echelon form. Every new equation is reduced by the
+/// earlier ones as soon as it arrives, so the work is spread out over the arrivals, and the
+/// solution is available immediately once there are enough independent equations.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, OnlineSolver};
+/// // x + y = 3 and x + 2y = 5
+/// let mut solver = OnlineSolver::new(2);
+/// solver.add_equation(&[GF(1), GF(1)], GF(3)).unwrap();
+/// assert!(!solver.is_solvable());
+/// solver.add_equation(&[GF(1), GF(2)], GF(5)).unwrap();
+///
+/// assert_eq!(solver.solution(), Some(vec![GF(1), GF(2)]));
+/// ```
+#[derive(Clone, Eq, PartialEq, Debug, Hash)]
+pub struct OnlineSolver {
+    unknowns: usize,
+    /// The independent equations, as coefficients and right-hand side, with their pivot
+    /// columns. The coefficient at every pivot column is 1 in its own equation and 0 in all
+    /// others.
+    equations: Vec<(Vec<GF>, GF)>,
+    pivots: Vec<usize>,
+}
+
+impl OnlineSolver {
+    /// Creates a solver for a system with `unknowns` unknowns and no equations.
+    pub fn new(unknowns: usize) -> Self {
+        OnlineSolver {
+            unknowns,
+            equations: Vec::new(),
+            pivots: Vec::new(),
+        }
+    }
+
+    /// Adds the equation `row[0] * x_0 + row[1] * x_1 + ... == rhs`. Returns whether the
+    /// equation was independent of the earlier ones. Dependent equations are dropped, without
+    /// checking whether they are consistent with the earlier ones.
+    ///
+    /// Returns [`GFError::DimensionMismatch`] if the row doesn't have a coefficient for every
+    /// unknown.
+    pub fn add_equation(&mut self, row: &[GF], rhs: GF) -> Result<bool, GFError> {
+        if row.len() != self.unknowns {
+            return Err(GFError::DimensionMismatch);
+        }
+
+        let mut row = row.to_vec();
+        let mut rhs = rhs;
+        for ((equation, equation_rhs), &pivot) in self.equations.iter().zip(&self.pivots) {
+            let factor = row[pivot];
+            for (r, &e) in row.iter_mut().zip(equation) {
+                *r -= factor * e;
+            }
+            rhs -= factor * *equation_rhs;
+        }
+
+        let pivot = match row.iter().position(|&c| c != GF(0)) {
+            Some(pivot) => pivot,
+            None => return Ok(false),
+        };
+        let inverse = row[pivot].multiplicative_inverse();
+        row.iter_mut().for_each(|c| *c *= inverse);
+        rhs *= inverse;
+
+        // Keep the system reduced, by eliminating the new pivot from the earlier equations.
+        for (equation, equation_rhs) in &mut self.equations {
+            let factor = equation[pivot];
+            for (e, &r) in equation.iter_mut().zip(&row) {
+                *e -= factor * r;
+            }
+            *equation_rhs -= factor * rhs;
+        }
+
+        self.equations.push((row, rhs));
+        self.pivots.push(pivot);
+        Ok(true)
+    }
+
+    /// The number of unknowns of the system.
+    pub fn unknowns(&self) -> usize {
+        self.unknowns
+    }
+
+    /// The number of independent equations that have been added.
+    pub fn rank(&self) -> usize {
+        self.equations.len()
+    }
+
+    /// Returns `true` if there are enough independent equations to determine every unknown.
+    pub fn is_solvable(&self) -> bool {
+        self.rank() == self.unknowns
+    }
+
+    /// The values of the unknowns, or `None` if there are not enough independent equations yet.
+    pub fn solution(&self) -> Option<Vec<GF>> {
+        if !self.is_solvable() {
+            return None;
+        }
+
+        let mut solution = vec![GF(0); self.unknowns];
+        for ((_, rhs), &pivot) in self.equations.iter().zip(&self.pivots) {
+            solution[pivot] = *rhs;
+        }
+        Some(solution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GFMatrix;
+
+    #[test]
+    fn incremental_solution_matches_batch() {
+        let a = GFMatrix::from_fn(6, 6, |i, j| GF::GENERATOR.pow((i * j) as u32));
+        let x = [GF(0x53), GF(0xca), GF(0x00), GF(0x01), GF(0xff), GF(0x13)];
+        let b: Vec<GF> = (0..6)
+            .map(|i| {
+                a.row(i)
+                    .iter()
+                    .zip(&x)
+                    .fold(GF(0), |acc, (&r, &x)| acc + r * x)
+            })
+            .collect();
+
+        let inverse = a.inverse().unwrap();
+        let batch: Vec<GF> = (0..6)
+            .map(|i| {
+                inverse
+                    .row(i)
+                    .iter()
+                    .zip(&b)
+                    .fold(GF(0), |acc, (&r, &b)| acc + r * b)
+            })
+            .collect();
+        assert_eq!(batch, x);
+
+        let mut solver = OnlineSolver::new(6);
+        for i in [3, 0, 5, 1, 4, 2] {
+            assert_eq!(solver.solution(), None);
+            assert_eq!(solver.add_equation(a.row(i), b[i]), Ok(true));
+        }
+        assert!(solver.is_solvable());
+        assert_eq!(solver.solution(), Some(batch));
+    }
+
+    #[test]
+    fn dependent_equations_are_dropped() {
+        let mut solver = OnlineSolver::new(3);
+        assert_eq!(solver.add_equation(&[GF(1), GF(2), GF(3)], GF(4)), Ok(true));
+        assert_eq!(solver.add_equation(&[GF(0), GF(1), GF(1)], GF(5)), Ok(true));
+
+        // The sum of the first two equations, and a multiple of the first.
+        assert_eq!(
+            solver.add_equation(&[GF(1), GF(3), GF(2)], GF(1)),
+            Ok(false)
+        );
+        assert_eq!(
+            solver.add_equation(&[GF(2), GF(4), GF(6)], GF(8)),
+            Ok(false)
+        );
+        assert_eq!(
+            solver.add_equation(&[GF(0), GF(0), GF(0)], GF(0)),
+            Ok(false)
+        );
+        assert_eq!(solver.rank(), 2);
+        assert!(!solver.is_solvable());
+
+        assert_eq!(solver.add_equation(&[GF(0), GF(0), GF(7)], GF(7)), Ok(true));
+        let solution = solver.solution().unwrap();
+        assert_eq!(solution[2], GF(1));
+        assert_eq!(solution[1] + solution[2], GF(5));
+    }
+
+    #[test]
+    fn wrong_number_of_coefficients() {
+        let mut solver = OnlineSolver::new(3);
+        assert_eq!(
+            solver.add_equation(&[GF(1), GF(2)], GF(4)),
+            Err(GFError::DimensionMismatch)
+        );
+        assert_eq!(solver.rank(), 0);
+    }
+}
@@ -0,0 +1,171 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! The Cayley tables of GF(2<sup>8</sup>), for tools that visualize or document the structure
+//! of the field.
+//!
+//! Every Cayley table has 65536 entries, and indexing it with secret values leaks them through
+//! the memory access pattern. Use the operators of [`GF`] for arithmetic instead.
+//!
+//! The AES S-box tables [`AES_SBOX`] and [`AES_INV_SBOX`] are calculated at compile time, for
+//! code that doesn't handle secrets and wants a lookup instead of a calculation. Indexing them
+//! isn't constant time either, so keys and plaintexts should go through the isochronous
+//! [`aes_sbox`](crate::aes::aes_sbox) and [`aes_inv_sbox`](crate::aes::aes_inv_sbox)
+//! instead.
+
+use crate::GF;
+
+/// The addition table, where `addition_table()[a][b]` is `GF(a) + GF(b)`.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::tables::addition_table;
+/// assert_eq!(addition_table()[0x53][0xca], 0x99);
+/// ```
+pub fn addition_table() -> [[u8; 256]; 256] {
+    table(|a, b| a + b)
+}
+
+/// The multiplication table, where `multiplication_table()[a][b]` is `GF(a) * GF(b)`.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::tables::multiplication_table;
+/// assert_eq!(multiplication_table()[0x53][0xca], 0x01);
+/// ```
+pub fn multiplication_table() -> [[u8; 256]; 256] {
+    table(|a, b| a * b)
+}
+
+/// The AES S-box as a table, where `AES_SBOX[x]` is [`aes_sbox`](crate::aes::aes_sbox)`(x)`.
+/// Indexing it with a secret value is not constant time.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::tables::AES_SBOX;
+/// assert_eq!(AES_SBOX[0x53], 0xed);
+/// ```
+pub const AES_SBOX: [u8; 256] = {
+    let mut table = [0; 256];
+    let mut x = 0;
+    while x < 256 {
+        table[x] = const_affine(const_inverse(x as u8));
+        x += 1;
+    }
+    table
+};
+
+/// The inverse AES S-box as a table, where `AES_INV_SBOX[x]` is
+/// [`aes_inv_sbox`](crate::aes::aes_inv_sbox)`(x)`. Indexing it with a secret value is not
+/// constant time.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::tables::AES_INV_SBOX;
+/// assert_eq!(AES_INV_SBOX[0xed], 0x53);
+/// ```
+pub const AES_INV_SBOX: [u8; 256] = {
+    let mut table = [0; 256];
+    let mut x = 0;
+    while x < 256 {
+        table[AES_SBOX[x] as usize] = x as u8;
+        x += 1;
+    }
+    table
+};
+
+/// The multiplicative inverse <i>a</i><sup>254</sup>, where 0 maps to 0, that can be evaluated
+/// at compile time.
+const fn const_inverse(a: u8) -> u8 {
+    let mut result = 1;
+    let mut square = a;
+    let mut exponent = 254;
+    while exponent != 0 {
+        if exponent & 1 == 1 {
+            result = GF(result).mul_const(GF(square)).0;
+        }
+        square = GF(square).mul_const(GF(square)).0;
+        exponent >>= 1;
+    }
+    result
+}
+
+/// The affine transformation of the AES S-box.
+const fn const_affine(b: u8) -> u8 {
+    b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4) ^ 0x63
+}
+
+fn table(operation: impl Fn(GF, GF) -> GF) -> [[u8; 256]; 256] {
+    let mut table = [[0; 256]; 256];
+
+    for (a, row) in table.iter_mut().enumerate() {
+        for (b, entry) in row.iter_mut().enumerate() {
+            *entry = operation(GF(a as u8), GF(b as u8)).0;
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_match_operators() {
+        let addition = addition_table();
+        let multiplication = multiplication_table();
+
+        for (a, b) in [
+            (0x53, 0xca),
+            (0x57, 0x83),
+            (0x00, 0xff),
+            (0x80, 0x02),
+            (0xff, 0xff),
+        ] {
+            assert_eq!(GF(addition[a][b]), GF(a as u8) + GF(b as u8));
+            assert_eq!(GF(multiplication[a][b]), GF(a as u8) * GF(b as u8));
+        }
+    }
+
+    #[test]
+    fn tables_are_symmetric() {
+        let addition = addition_table();
+        let multiplication = multiplication_table();
+
+        for a in 0..256 {
+            assert_eq!(addition[a][a], 0);
+            for b in 0..256 {
+                assert_eq!(addition[a][b], addition[b][a]);
+                assert_eq!(multiplication[a][b], multiplication[b][a]);
+            }
+        }
+    }
+
+    #[test]
+    fn sbox_tables_match_aes() {
+        for x in 0..=255 {
+            assert_eq!(AES_SBOX[x as usize], crate::aes::aes_sbox(x));
+            assert_eq!(AES_INV_SBOX[x as usize], crate::aes::aes_inv_sbox(x));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+/*
+ *  Copyright (c) 2020 Thijs Raymakers
+ *
+ *  Permission is hereby granted, free of charge, to any person obtaining a copy
+ *  of this software and associated documentation files (the "Software"), to deal
+ *  in the Software without restriction, including without limitation the rights
+ *  to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ *  copies of the Software, and to permit persons to whom the Software is
+ *  furnished to do so, subject to the following conditions:
+ *
+ *  The above copyright notice and this permission notice shall be included in all
+ *  copies or substantial portions of the Software.
+ *
+ *  THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ *  IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ *  FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ *  AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ *  LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ *  OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ *  SOFTWARE.
+ */
+
+//! Fixed size vectors with elements in GF(2<sup>8</sup>).
+
+use crate::GF;
+use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// Vector of `N` elements of the Galois field GF(2<sup>8</sup>), such as a column of the AES
+/// state. It doesn't need an allocator.
+///
+/// # Example
+/// ```
+/// # use isochronous_finite_fields::{GF, GFVec};
+/// let a = GFVec([GF(1), GF(2), GF(3), GF(4)]);
+/// let b = GFVec([GF(4), GF(3), GF(2), GF(1)]);
+///
+/// assert_eq!(a + b, GFVec([GF(5), GF(1), GF(1), GF(5)]));
+/// assert_eq!(a * GF(2), GFVec([GF(2), GF(4), GF(6), GF(8)]));
+/// ```
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub struct GFVec<const N: usize>(pub [GF; N]);
+
+impl<const N: usize> GFVec<N> {
+    /// The dot product, which is the sum of the elementwise products.
+    pub fn dot(&self, rhs: &Self) -> GF {
+        self.0
+            .iter()
+            .zip(&rhs.0)
+            .fold(GF(0), |acc, (&a, &b)| acc + a * b)
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for GFVec<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        GFVec(bytes.map(GF))
+    }
+}
+
+impl<const N: usize> From<GFVec<N>> for [u8; N] {
+    fn from(vector: GFVec<N>) -> Self {
+        vector.0.map(|x| x.0)
+    }
+}
+
+/// Vectors are added elementwise.
+impl<const N: usize> Add for GFVec<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        GFVec(core::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<const N: usize> AddAssign for GFVec<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.add(rhs)
+    }
+}
+
+/// Vectors are subtracted elementwise, which is equal to adding them.
+impl<const N: usize> Sub for GFVec<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.add(rhs)
+    }
+}
+
+impl<const N: usize> SubAssign for GFVec<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.add_assign(rhs)
+    }
+}
+
+/// Multiplying a vector by a scalar multiplies every element by the scalar.
+impl<const N: usize> Mul<GF> for GFVec<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: GF) -> Self::Output {
+        GFVec(self.0.map(|x| x * rhs))
+    }
+}
+
+impl<const N: usize> MulAssign<GF> for GFVec<N> {
+    fn mul_assign(&mut self, rhs: GF) {
+        *self = self.mul(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elementwise_addition() {
+        let mut a = GFVec([GF(0x22), GF(0x93), GF(0x00), GF(0xff)]);
+        a += GFVec([GF(0x81), GF(0x5b), GF(0x53), GF(0xff)]);
+        assert_eq!(a, GFVec([GF(0xa3), GF(0xc8), GF(0x53), GF(0x00)]));
+
+        a -= GFVec([GF(0x81), GF(0x5b), GF(0x53), GF(0xff)]);
+        assert_eq!(a, GFVec([GF(0x22), GF(0x93), GF(0x00), GF(0xff)]));
+    }
+
+    #[test]
+    fn scalar_multiplication() {
+        let mut a = GFVec([GF(0x57), GF(0x01), GF(0x00), GF(0x53)]);
+        a *= GF(0x83);
+        assert_eq!(
+            a,
+            GFVec([GF(0xc1), GF(0x83), GF(0x00), GF(0x53) * GF(0x83)])
+        );
+    }
+
+    #[test]
+    fn byte_array_round_trip() {
+        let bytes = [0x53, 0xca, 0x00, 0xff];
+        let vector = GFVec::from(bytes);
+        assert_eq!(vector, GFVec([GF(0x53), GF(0xca), GF(0x00), GF(0xff)]));
+        assert_eq!(<[u8; 4]>::from(vector), bytes);
+    }
+
+    #[test]
+    fn dot_product() {
+        let a = GFVec([GF(0x57), GF(0x53), GF(0x02), GF(0x00)]);
+        let b = GFVec([GF(0x83), GF(0xca), GF(0x01), GF(0xff)]);
+        // 0x57 * 0x83 + 0x53 * 0xca + 0x02 * 0x01 + 0
+        assert_eq!(a.dot(&b), GF(0xc1) + GF(0x01) + GF(0x02));
+        assert_eq!(GFVec::<0>([]).dot(&GFVec([])), GF(0));
+    }
+}